@@ -5,10 +5,10 @@
 # shellcheck disable=SC2034
 
 # Test all these features with "std" enabled.
-FEATURES_WITH_STD="global-context global-context-less-secure lowmemory rand recovery serde"
+FEATURES_WITH_STD="cose global-context global-context-less-secure lowmemory nostr rand rayon recovery serde"
 
 # Test all these features without "std" enabled.
-FEATURES_WITHOUT_STD="global-context global-context-less-secure lowmemory rand recovery serde alloc"
+FEATURES_WITHOUT_STD="cose global-context global-context-less-secure lowmemory nostr rand recovery serde alloc"
 
 # Run these examples.
 EXAMPLES="sign_verify:std sign_verify_recovery:std,recovery generate_keys:rand,std"
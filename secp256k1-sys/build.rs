@@ -56,7 +56,8 @@ fn main() {
         .file("depend/secp256k1/contrib/lax_der_parsing.c")
         .file("depend/secp256k1/src/precomputed_ecmult_gen.c")
         .file("depend/secp256k1/src/precomputed_ecmult.c")
-        .file("depend/secp256k1/src/secp256k1.c");
+        // Includes `secp256k1.c` and exports its internal hash functions.
+        .file("depend/hash_shims.c");
 
     if base_config.try_compile("libsecp256k1.a").is_err() {
         // Some embedded platforms may not have, eg, string.h available, so if the build fails
@@ -0,0 +1,54 @@
+/* Exports the hash functions that libsecp256k1 uses internally.
+ *
+ * The library declares them `static`, so this file is compiled in place of `secp256k1.c` and
+ * includes it, which lets the wrappers below call them without a second copy of the code. */
+
+#include "src/secp256k1.c"
+
+void rustsecp256k1_v0_11_shim_sha256_initialize(rustsecp256k1_v0_11_sha256 *hash) {
+    rustsecp256k1_v0_11_sha256_initialize(hash);
+}
+
+void rustsecp256k1_v0_11_shim_sha256_write(rustsecp256k1_v0_11_sha256 *hash, const unsigned char *data, size_t size) {
+    rustsecp256k1_v0_11_sha256_write(hash, data, size);
+}
+
+void rustsecp256k1_v0_11_shim_sha256_finalize(rustsecp256k1_v0_11_sha256 *hash, unsigned char *out32) {
+    rustsecp256k1_v0_11_sha256_finalize(hash, out32);
+}
+
+void rustsecp256k1_v0_11_shim_sha256_clear(rustsecp256k1_v0_11_sha256 *hash) {
+    rustsecp256k1_v0_11_sha256_clear(hash);
+}
+
+void rustsecp256k1_v0_11_shim_hmac_sha256_initialize(rustsecp256k1_v0_11_hmac_sha256 *hash, const unsigned char *key, size_t keylen) {
+    rustsecp256k1_v0_11_hmac_sha256_initialize(hash, key, keylen);
+}
+
+void rustsecp256k1_v0_11_shim_hmac_sha256_write(rustsecp256k1_v0_11_hmac_sha256 *hash, const unsigned char *data, size_t size) {
+    rustsecp256k1_v0_11_hmac_sha256_write(hash, data, size);
+}
+
+void rustsecp256k1_v0_11_shim_hmac_sha256_finalize(rustsecp256k1_v0_11_hmac_sha256 *hash, unsigned char *out32) {
+    rustsecp256k1_v0_11_hmac_sha256_finalize(hash, out32);
+}
+
+void rustsecp256k1_v0_11_shim_hmac_sha256_clear(rustsecp256k1_v0_11_hmac_sha256 *hash) {
+    rustsecp256k1_v0_11_hmac_sha256_clear(hash);
+}
+
+void rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_initialize(rustsecp256k1_v0_11_rfc6979_hmac_sha256 *rng, const unsigned char *key, size_t keylen) {
+    rustsecp256k1_v0_11_rfc6979_hmac_sha256_initialize(rng, key, keylen);
+}
+
+void rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_generate(rustsecp256k1_v0_11_rfc6979_hmac_sha256 *rng, unsigned char *out, size_t outlen) {
+    rustsecp256k1_v0_11_rfc6979_hmac_sha256_generate(rng, out, outlen);
+}
+
+void rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_finalize(rustsecp256k1_v0_11_rfc6979_hmac_sha256 *rng) {
+    rustsecp256k1_v0_11_rfc6979_hmac_sha256_finalize(rng);
+}
+
+void rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_clear(rustsecp256k1_v0_11_rfc6979_hmac_sha256 *rng) {
+    rustsecp256k1_v0_11_rfc6979_hmac_sha256_clear(rng);
+}
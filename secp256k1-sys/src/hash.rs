@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! # FFI of the internal hash functions
+//!
+//! libsecp256k1 does not export its SHA-256, HMAC-SHA256 and RFC 6979 HMAC-DRBG implementations,
+//! so the bundled library is built with small wrappers around them (see `depend/hash_shims.c`).
+//! This module is therefore not available when linking against a system library with
+//! `rust_secp_no_symbol_renaming`.
+
+use crate::types::*;
+
+/// Library-internal state of an incremental SHA-256 computation.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Sha256 {
+    s: [u32; 8],
+    buf: [c_uchar; 64],
+    bytes: u64,
+}
+
+impl Sha256 {
+    /// Creates a new (zeroed) state usable for the FFI interface.
+    ///
+    /// It must be initialized with [`secp256k1_sha256_initialize`] before use.
+    pub fn new() -> Sha256 { Sha256 { s: [0; 8], buf: [0; 64], bytes: 0 } }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self { Sha256::new() }
+}
+
+/// Library-internal state of an incremental HMAC-SHA256 computation.
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    /// Creates a new (zeroed) state usable for the FFI interface.
+    ///
+    /// It must be initialized with [`secp256k1_hmac_sha256_initialize`] before use.
+    pub fn new() -> HmacSha256 { HmacSha256 { inner: Sha256::new(), outer: Sha256::new() } }
+}
+
+/// Library-internal state of the RFC 6979 HMAC-SHA256 deterministic random bit generator.
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+pub struct Rfc6979HmacSha256 {
+    v: [c_uchar; 32],
+    k: [c_uchar; 32],
+    retry: c_int,
+}
+
+impl Rfc6979HmacSha256 {
+    /// Creates a new (zeroed) state usable for the FFI interface.
+    ///
+    /// It must be initialized with [`secp256k1_rfc6979_hmac_sha256_initialize`] before use.
+    pub fn new() -> Rfc6979HmacSha256 { Rfc6979HmacSha256 { v: [0; 32], k: [0; 32], retry: 0 } }
+}
+
+extern "C" {
+    #[link_name = "rustsecp256k1_v0_11_shim_sha256_initialize"]
+    pub fn secp256k1_sha256_initialize(hash: *mut Sha256);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_sha256_write"]
+    pub fn secp256k1_sha256_write(hash: *mut Sha256, data: *const c_uchar, size: size_t);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_sha256_finalize"]
+    pub fn secp256k1_sha256_finalize(hash: *mut Sha256, out32: *mut c_uchar);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_sha256_clear"]
+    pub fn secp256k1_sha256_clear(hash: *mut Sha256);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_hmac_sha256_initialize"]
+    pub fn secp256k1_hmac_sha256_initialize(
+        hash: *mut HmacSha256,
+        key: *const c_uchar,
+        keylen: size_t,
+    );
+
+    #[link_name = "rustsecp256k1_v0_11_shim_hmac_sha256_write"]
+    pub fn secp256k1_hmac_sha256_write(hash: *mut HmacSha256, data: *const c_uchar, size: size_t);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_hmac_sha256_finalize"]
+    pub fn secp256k1_hmac_sha256_finalize(hash: *mut HmacSha256, out32: *mut c_uchar);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_hmac_sha256_clear"]
+    pub fn secp256k1_hmac_sha256_clear(hash: *mut HmacSha256);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_initialize"]
+    pub fn secp256k1_rfc6979_hmac_sha256_initialize(
+        rng: *mut Rfc6979HmacSha256,
+        key: *const c_uchar,
+        keylen: size_t,
+    );
+
+    #[link_name = "rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_generate"]
+    pub fn secp256k1_rfc6979_hmac_sha256_generate(
+        rng: *mut Rfc6979HmacSha256,
+        out: *mut c_uchar,
+        outlen: size_t,
+    );
+
+    #[link_name = "rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_finalize"]
+    pub fn secp256k1_rfc6979_hmac_sha256_finalize(rng: *mut Rfc6979HmacSha256);
+
+    #[link_name = "rustsecp256k1_v0_11_shim_rfc6979_hmac_sha256_clear"]
+    pub fn secp256k1_rfc6979_hmac_sha256_clear(rng: *mut Rfc6979HmacSha256);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_abc() {
+        let mut hash = Sha256::new();
+        let mut out = [0u8; 32];
+        unsafe {
+            secp256k1_sha256_initialize(&mut hash);
+            secp256k1_sha256_write(&mut hash, b"abc".as_ptr(), 3);
+            secp256k1_sha256_finalize(&mut hash, out.as_mut_ptr());
+        }
+        assert_eq!(
+            out,
+            [
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+                0xf2, 0x00, 0x15, 0xad,
+            ]
+        );
+    }
+}
@@ -18,6 +18,8 @@ extern crate alloc;
 #[cfg(secp256k1_fuzz)]
 const THIS_UNUSED_CONSTANT_IS_YOUR_WARNING_THAT_ALL_THE_CRYPTO_IN_THIS_LIB_IS_DISABLED_FOR_FUZZING: usize = 0;
 
+#[cfg(not(rust_secp_no_symbol_renaming))]
+pub mod hash;
 mod macros;
 pub mod types;
 
@@ -116,6 +116,8 @@ find "$DIR" \
     -print0 | xargs -0 sed -i "/^#include/! s/ecdsa_signature_parse_der_lax/rustsecp256k1_v${SECP_VENDOR_VERSION_CODE}_ecdsa_signature_parse_der_lax/g"
 
 cd "$SECP_SYS"
+# Update the prefix of the exported hash functions.
+sed -i -r "s/rustsecp256k1_v[0-9]+_[0-9]+_/rustsecp256k1_v${SECP_VENDOR_VERSION_CODE}_/g" depend/hash_shims.c
 # Update the `links = ` in the manifest file.
 sed -i -r "s/^links = \".*\"$/links = \"rustsecp256k1_v${SECP_VENDOR_VERSION_CODE}\"/" Cargo.toml
 # Update the extern references in the Rust FFI source files.
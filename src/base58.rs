@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Minimal base58 encoding and decoding, with the Bitcoin alphabet, into caller-provided buffers.
+
+use core::str;
+
+const ALPHABET: [u8; 58] = *b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// An error decoding base58.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DecodeError {
+    /// The input contains a character outside the base58 alphabet.
+    InvalidCharacter,
+    /// The decoded data does not fit in the target buffer.
+    TooLong,
+}
+
+/// Encodes `src` as base58 into `target`, returning the written part as a `str`.
+///
+/// Returns an error if the target buffer isn't big enough.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode<'a>(src: &[u8], target: &'a mut [u8]) -> Result<&'a str, ()> {
+    // Base58 digits of the number, least significant first.
+    let mut len = 0;
+    for &byte in src {
+        let mut carry = u32::from(byte);
+        for digit in &mut target[..len] {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            *target.get_mut(len).ok_or(())? = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+    // Each leading zero byte is encoded as a leading '1'.
+    for _ in src.iter().take_while(|&&b| b == 0) {
+        *target.get_mut(len).ok_or(())? = 0;
+        len += 1;
+    }
+
+    let result = &mut target[..len];
+    result.reverse();
+    for digit in result.iter_mut() {
+        *digit = ALPHABET[usize::from(*digit)];
+    }
+    debug_assert!(str::from_utf8(result).is_ok());
+    unsafe { Ok(str::from_utf8_unchecked(result)) }
+}
+
+/// Decodes base58 from `src` into `target`, returning the number of bytes written.
+pub(crate) fn decode(src: &str, target: &mut [u8]) -> Result<usize, DecodeError> {
+    // The decoded number is accumulated big-endian at the end of `target`.
+    let end = target.len();
+    let mut len = 0;
+    for c in src.bytes() {
+        let digit = ALPHABET.iter().position(|&a| a == c).ok_or(DecodeError::InvalidCharacter)?;
+        let mut carry = digit as u32;
+        for byte in target[end - len..].iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            if len == end {
+                return Err(DecodeError::TooLong);
+            }
+            len += 1;
+            target[end - len] = carry as u8;
+            carry >>= 8;
+        }
+    }
+
+    // Each leading '1' is decoded as a leading zero byte.
+    let zeros = src.bytes().take_while(|&c| c == b'1').count();
+    if zeros + len > end {
+        return Err(DecodeError::TooLong);
+    }
+    target.copy_within(end - len.., zeros);
+    for byte in &mut target[..zeros] {
+        *byte = 0;
+    }
+    Ok(zeros + len)
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let vectors: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (&[0], "1"),
+            (&[0, 0, 1, 2], "115T"),
+            (b"hello world", "StV1DL6CwTryKyV"),
+            (
+                &hex!("00010966776006953d5567439e5e39f86a0d273beed61967f6"),
+                "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM",
+            ),
+        ];
+        for (raw, encoded) in vectors {
+            let mut out = [0u8; 25];
+            let len = decode(encoded, &mut out).unwrap();
+            assert_eq!(&out[..len], raw);
+
+            #[cfg(feature = "alloc")]
+            {
+                let mut buf = [0u8; 34];
+                assert_eq!(encode(raw, &mut buf).unwrap(), encoded);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let mut out = [0u8; 4];
+        assert_eq!(decode("1O", &mut out), Err(DecodeError::InvalidCharacter));
+        assert_eq!(decode("115T", &mut out[..3]), Err(DecodeError::TooLong));
+        assert_eq!(decode("StV1DL6CwTryKyV", &mut out), Err(DecodeError::TooLong));
+        #[cfg(feature = "alloc")]
+        assert!(encode(b"hello world", &mut [0u8; 14]).is_err());
+    }
+}
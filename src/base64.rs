@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Minimal base64 (RFC 4648) encoding and decoding into caller-provided buffers.
+
+use core::str;
+
+#[cfg(any(feature = "alloc", feature = "recovery"))]
+const TABLE: [u8; 64] = *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+#[cfg(any(feature = "alloc", feature = "serde"))]
+const URL_SAFE_TABLE: [u8; 64] =
+    *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Returns the length of the padded base64 encoding of `len` bytes.
+pub(crate) const fn encoded_len(len: usize) -> usize { (len + 2) / 3 * 4 }
+
+/// Returns the length of the unpadded base64url encoding of `len` bytes.
+pub(crate) const fn url_safe_encoded_len(len: usize) -> usize { (len * 4 + 2) / 3 }
+
+/// Encodes `src` as padded base64 into `target`, returning the written part as a `str`.
+///
+/// Returns an error if the target buffer isn't big enough.
+#[cfg(any(feature = "alloc", feature = "recovery"))]
+pub(crate) fn encode<'a>(src: &[u8], target: &'a mut [u8]) -> Result<&'a str, ()> {
+    encode_with(src, target, &TABLE, true)
+}
+
+/// Encodes `src` as unpadded base64url (RFC 4648 section 5) into `target`, returning the written
+/// part as a `str`.
+///
+/// Returns an error if the target buffer isn't big enough.
+#[cfg(any(feature = "alloc", feature = "serde"))]
+pub(crate) fn encode_url_safe<'a>(src: &[u8], target: &'a mut [u8]) -> Result<&'a str, ()> {
+    encode_with(src, target, &URL_SAFE_TABLE, false)
+}
+
+fn encode_with<'a>(
+    src: &[u8],
+    target: &'a mut [u8],
+    table: &[u8; 64],
+    pad: bool,
+) -> Result<&'a str, ()> {
+    let len = if pad { encoded_len(src.len()) } else { url_safe_encoded_len(src.len()) };
+    if target.len() < len {
+        return Err(());
+    }
+
+    let mut written = 0;
+    for chunk in src.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                target[written] = table[(n >> (18 - 6 * i)) as usize & 0x3f];
+            } else if pad {
+                target[written] = b'=';
+            } else {
+                break;
+            }
+            written += 1;
+        }
+    }
+    debug_assert_eq!(written, len);
+    let result = &target[..len];
+    debug_assert!(str::from_utf8(result).is_ok());
+    unsafe { Ok(str::from_utf8_unchecked(result)) }
+}
+
+/// Decodes padded base64 from `src` into `target`, returning the number of bytes written.
+///
+/// Returns an error on invalid characters or padding, or if the target buffer isn't big enough.
+#[cfg(any(feature = "alloc", feature = "recovery"))]
+pub(crate) fn decode(src: &str, target: &mut [u8]) -> Result<usize, ()> {
+    if src.len() % 4 != 0 {
+        return Err(());
+    }
+    decode_with(src.as_bytes(), target, &TABLE, true)
+}
+
+/// Returns the number of bytes that padded base64 `src` decodes to, without storing them.
+///
+/// Returns an error on invalid characters or padding, like [`decode`].
+#[cfg(feature = "recovery")]
+pub(crate) fn decoded_len(src: &str) -> Result<usize, ()> {
+    if src.len() % 4 != 0 {
+        return Err(());
+    }
+    let n_chunks = src.len() / 4;
+    let mut len = 0;
+    for (idx, chunk) in src.as_bytes().chunks(4).enumerate() {
+        // Each chunk is decoded as if it were the last one, so padding is checked here.
+        if idx + 1 != n_chunks && chunk.contains(&b'=') {
+            return Err(());
+        }
+        len += decode_with(chunk, &mut [0u8; 3], &TABLE, true)?;
+    }
+    Ok(len)
+}
+
+/// Decodes unpadded base64url (RFC 4648 section 5) from `src` into `target`, returning the
+/// number of bytes written.
+///
+/// Returns an error on invalid characters or padding, or if the target buffer isn't big enough.
+#[cfg(any(feature = "alloc", feature = "serde"))]
+pub(crate) fn decode_url_safe(src: &str, target: &mut [u8]) -> Result<usize, ()> {
+    if src.len() % 4 == 1 {
+        return Err(());
+    }
+    decode_with(src.as_bytes(), target, &URL_SAFE_TABLE, false)
+}
+
+fn decode_with(src: &[u8], target: &mut [u8], table: &[u8; 64], padded: bool) -> Result<usize, ()> {
+    let mut written = 0;
+    let n_chunks = (src.len() + 3) / 4;
+    for (idx, chunk) in src.chunks(4).enumerate() {
+        let pad = if padded { chunk.iter().rev().take_while(|&&c| c == b'=').count() } else { 0 };
+        if pad > 2 || (pad > 0 && idx + 1 != n_chunks) {
+            return Err(());
+        }
+        // Unpadded chunks at the end are as long as padded ones without the padding.
+        let missing = 4 - chunk.len() + pad;
+
+        let mut n = 0u32;
+        for &c in &chunk[..4 - missing] {
+            let v = table.iter().position(|&t| t == c).ok_or(())?;
+            n = (n << 6) | v as u32;
+        }
+        n <<= 6 * missing as u32;
+
+        let bytes = n.to_be_bytes();
+        let out_len = 3 - missing;
+        // Reject non-canonical encodings whose discarded bits are set.
+        if bytes[1 + out_len..].iter().any(|&b| b != 0) {
+            return Err(());
+        }
+        if target.len() < written + out_len {
+            return Err(());
+        }
+        target[written..written + out_len].copy_from_slice(&bytes[1..1 + out_len]);
+        written += out_len;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "alloc", feature = "recovery"))]
+    fn rfc4648_vectors() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+        for (raw, encoded) in vectors {
+            let mut buf = [0u8; 8];
+            assert_eq!(encode(raw, &mut buf).unwrap(), encoded);
+
+            let mut out = [0u8; 6];
+            let len = decode(encoded, &mut out).unwrap();
+            assert_eq!(&out[..len], raw);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "alloc", feature = "recovery"))]
+    fn invalid() {
+        let mut out = [0u8; 6];
+        assert!(decode("Zg=", &mut out).is_err());
+        assert!(decode("Zg=a", &mut out).is_err());
+        assert!(decode("Zg==Zg==", &mut out).is_err());
+        assert!(decode("Zh==", &mut out).is_err());
+        assert!(decode("Zm9v!A==", &mut out).is_err());
+        assert!(decode("Zm9vYmFy", &mut out[..5]).is_err());
+        assert!(encode(b"foo", &mut [0u8; 3]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "recovery")]
+    fn decoded_len() {
+        assert_eq!(super::decoded_len(""), Ok(0));
+        assert_eq!(super::decoded_len("Zm9vYg=="), Ok(4));
+        assert_eq!(super::decoded_len("Zm9vYmFy"), Ok(6));
+        assert!(super::decoded_len("Zg=").is_err());
+        assert!(super::decoded_len("Zg==Zg==").is_err());
+        assert!(super::decoded_len("Zm9v!A==").is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "alloc", feature = "serde"))]
+    fn url_safe() {
+        let vectors: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (b"f", "Zg"),
+            (b"fo", "Zm8"),
+            (b"foo", "Zm9v"),
+            (&[0xfb, 0xff, 0xbf], "-_-_"),
+        ];
+        for (raw, encoded) in vectors {
+            let mut buf = [0u8; 4];
+            assert_eq!(encode_url_safe(raw, &mut buf).unwrap(), encoded);
+
+            let mut out = [0u8; 3];
+            let len = decode_url_safe(encoded, &mut out).unwrap();
+            assert_eq!(&out[..len], raw);
+        }
+
+        let mut out = [0u8; 6];
+        assert!(decode_url_safe("Zg==", &mut out).is_err());
+        assert!(decode_url_safe("Zm9vY", &mut out).is_err());
+        assert!(decode_url_safe("Zh", &mut out).is_err());
+        assert!(decode_url_safe("+/+/", &mut out).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Minimal bech32 (BIP-173) encoding and decoding of byte payloads.
+//!
+//! Only the original bech32 checksum is supported, not bech32m, and strings are not limited to
+//! 90 characters, as NIP-19 does not limit them either.
+
+use crate::alloc::string::String;
+use crate::alloc::vec::Vec;
+
+const CHARSET: [u8; 32] = *b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The length of the checksum, in characters.
+const CHECKSUM_SIZE: usize = 6;
+
+/// An error decoding bech32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DecodeError {
+    /// The string has no separator, an empty or invalid human-readable part or mixed case.
+    InvalidFormat,
+    /// The data part contains a character outside the bech32 alphabet.
+    InvalidCharacter,
+    /// The checksum does not match.
+    IncorrectChecksum,
+    /// The data part does not convert to whole bytes.
+    InvalidPadding,
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk = 1u32;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+/// Expands the human-readable part for the checksum computation.
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes().map(|c| c >> 5).chain(Some(0)).chain(hrp.bytes().map(|c| c & 0x1f))
+}
+
+/// Encodes `data` with the lowercase human-readable part `hrp`.
+pub(crate) fn encode(hrp: &str, data: &[u8]) -> String {
+    // Convert to 5-bit groups, padding the last one with zero bits.
+    let mut values = Vec::with_capacity((data.len() * 8 + 4) / 5 + CHECKSUM_SIZE);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &byte in data {
+        acc = (acc << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values.push((acc >> bits) as u8 & 0x1f);
+        }
+    }
+    if bits > 0 {
+        values.push((acc << (5 - bits)) as u8 & 0x1f);
+    }
+
+    let checksum =
+        polymod(hrp_expand(hrp).chain(values.iter().copied()).chain([0; CHECKSUM_SIZE])) ^ 1;
+    for i in 0..CHECKSUM_SIZE {
+        values.push((checksum >> (5 * (5 - i))) as u8 & 0x1f);
+    }
+
+    let mut ret = String::with_capacity(hrp.len() + 1 + values.len());
+    ret.push_str(hrp);
+    ret.push('1');
+    ret.extend(values.iter().map(|&v| char::from(CHARSET[usize::from(v)])));
+    ret
+}
+
+/// Decodes a bech32 string, returning its lowercase human-readable part and its data.
+pub(crate) fn decode(s: &str) -> Result<(String, Vec<u8>), DecodeError> {
+    if s.bytes().any(|c| c.is_ascii_lowercase()) && s.bytes().any(|c| c.is_ascii_uppercase()) {
+        return Err(DecodeError::InvalidFormat);
+    }
+    let s = s.to_ascii_lowercase();
+    let sep = s.rfind('1').ok_or(DecodeError::InvalidFormat)?;
+    let (hrp, data) = (&s[..sep], &s[sep + 1..]);
+    if hrp.is_empty() || !hrp.bytes().all(|c| (33..=126).contains(&c)) {
+        return Err(DecodeError::InvalidFormat);
+    }
+    if data.len() < CHECKSUM_SIZE {
+        return Err(DecodeError::IncorrectChecksum);
+    }
+
+    let values = data
+        .bytes()
+        .map(|c| CHARSET.iter().position(|&a| a == c).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(DecodeError::InvalidCharacter)?;
+    if polymod(hrp_expand(hrp).chain(values.iter().copied())) != 1 {
+        return Err(DecodeError::IncorrectChecksum);
+    }
+
+    // Convert from 5-bit groups, rejecting more than 4 bits of padding or non-zero padding.
+    let values = &values[..values.len() - CHECKSUM_SIZE];
+    let mut ret = Vec::with_capacity(values.len() * 5 / 8);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &value in values {
+        acc = ((acc << 5) | u32::from(value)) & 0xfff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            ret.push((acc >> bits) as u8);
+        }
+    }
+    if bits >= 5 || acc & ((1 << bits) - 1) != 0 {
+        return Err(DecodeError::InvalidPadding);
+    }
+    Ok((String::from(hrp), ret))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn bip173_valid() {
+        for s in [
+            "A12UEL5L",
+            "a12uel5l",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+            "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+        ] {
+            let (hrp, data) = decode(s).unwrap();
+            assert_eq!(encode(&hrp, &data), s.to_ascii_lowercase());
+        }
+    }
+
+    #[test]
+    fn bip173_invalid() {
+        assert_eq!(decode("pzry9x0s0muk"), Err(DecodeError::InvalidFormat));
+        assert_eq!(decode("1pzry9x0s0muk"), Err(DecodeError::InvalidFormat));
+        assert_eq!(decode("x1b4n0q5v"), Err(DecodeError::InvalidCharacter));
+        assert_eq!(decode("li1dgmt3"), Err(DecodeError::IncorrectChecksum));
+        assert_eq!(decode("A1G7SGD8"), Err(DecodeError::IncorrectChecksum));
+        assert_eq!(decode("A12UEL5l"), Err(DecodeError::InvalidFormat));
+    }
+
+    #[test]
+    fn roundtrip() {
+        for len in 0..40 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            let encoded = encode("test", &data);
+            assert_eq!(decode(&encoded), Ok((String::from("test"), data)));
+        }
+    }
+}
@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Minimal CBOR (RFC 8949) encoding and decoding of the data items used by COSE.
+//!
+//! Only definite-length items are supported. Integers are limited to the range of `i64`.
+
+#[cfg(feature = "alloc")]
+use crate::alloc::vec::Vec;
+
+/// The major type of unsigned integers.
+pub(crate) const UNSIGNED: u8 = 0;
+/// The major type of negative integers.
+pub(crate) const NEGATIVE: u8 = 1;
+/// The major type of byte strings.
+pub(crate) const BYTES: u8 = 2;
+/// The major type of text strings.
+pub(crate) const TEXT: u8 = 3;
+/// The major type of arrays.
+pub(crate) const ARRAY: u8 = 4;
+/// The major type of maps.
+pub(crate) const MAP: u8 = 5;
+/// The major type of tagged items.
+pub(crate) const TAG: u8 = 6;
+/// The major type of simple values and floats.
+pub(crate) const SIMPLE: u8 = 7;
+
+/// The simple value `false`.
+pub(crate) const FALSE: u64 = 20;
+/// The simple value `true`.
+pub(crate) const TRUE: u64 = 21;
+/// The simple value `null`.
+#[cfg(feature = "alloc")]
+pub(crate) const NULL: u64 = 22;
+
+/// A destination for encoded CBOR.
+pub(crate) trait Encoder {
+    /// Appends raw bytes.
+    fn write(&mut self, data: &[u8]);
+
+    /// Writes the head of an item in its shortest form.
+    fn head(&mut self, major: u8, arg: u64) {
+        let major = major << 5;
+        if arg < 24 {
+            self.write(&[major | arg as u8]);
+        } else if let Ok(arg) = u8::try_from(arg) {
+            self.write(&[major | 24, arg]);
+        } else if let Ok(arg) = u16::try_from(arg) {
+            self.write(&[major | 25]);
+            self.write(&arg.to_be_bytes());
+        } else if let Ok(arg) = u32::try_from(arg) {
+            self.write(&[major | 26]);
+            self.write(&arg.to_be_bytes());
+        } else {
+            self.write(&[major | 27]);
+            self.write(&arg.to_be_bytes());
+        }
+    }
+
+    /// Writes an integer.
+    fn int(&mut self, value: i64) {
+        if value < 0 {
+            self.head(NEGATIVE, !value as u64);
+        } else {
+            self.head(UNSIGNED, value as u64);
+        }
+    }
+
+    /// Writes a byte string.
+    fn bytes(&mut self, data: &[u8]) {
+        self.head(BYTES, data.len() as u64);
+        self.write(data);
+    }
+
+    /// Writes a text string.
+    #[cfg(feature = "alloc")]
+    fn text(&mut self, s: &str) {
+        self.head(TEXT, s.len() as u64);
+        self.write(s.as_bytes());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoder for Vec<u8> {
+    fn write(&mut self, data: &[u8]) { self.extend_from_slice(data) }
+}
+
+/// Writes CBOR into a buffer of exactly the right size.
+pub(crate) struct SliceEncoder<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> SliceEncoder<'a> {
+    pub(crate) fn new(buf: &'a mut [u8]) -> Self { SliceEncoder { buf, pos: 0 } }
+
+    pub(crate) fn finish(self) {
+        debug_assert_eq!(self.pos, self.buf.len());
+    }
+}
+
+impl Encoder for SliceEncoder<'_> {
+    fn write(&mut self, data: &[u8]) {
+        self.buf[self.pos..self.pos + data.len()].copy_from_slice(data);
+        self.pos += data.len();
+    }
+}
+
+/// Reads CBOR data items from a buffer.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self { Reader { data } }
+
+    /// Returns the major type of the next item without consuming it.
+    pub(crate) fn peek_major(&self) -> Result<u8, ()> {
+        self.data.first().map(|b| b >> 5).ok_or(())
+    }
+
+    /// Returns whether the next item is the simple value `value`, consuming it if it is.
+    pub(crate) fn simple(&mut self, value: u64) -> Result<bool, ()> {
+        let ret = self.data.first().ok_or(())? == &((SIMPLE << 5) | value as u8);
+        if ret {
+            self.data = &self.data[1..];
+        }
+        Ok(ret)
+    }
+
+    /// Reads the head of an item, returning its major type and argument.
+    pub(crate) fn head(&mut self) -> Result<(u8, u64), ()> {
+        let (&initial, rest) = self.data.split_first().ok_or(())?;
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let len = match info {
+            0..=23 => 0,
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            // Reserved values and indefinite lengths.
+            _ => return Err(()),
+        };
+        if rest.len() < len {
+            return Err(());
+        }
+        let arg = if len == 0 {
+            u64::from(info)
+        } else {
+            let mut buf = [0u8; 8];
+            buf[8 - len..].copy_from_slice(&rest[..len]);
+            u64::from_be_bytes(buf)
+        };
+        self.data = &rest[len..];
+        Ok((major, arg))
+    }
+
+    /// Reads the head of an item of major type `major` and returns its argument.
+    fn expect(&mut self, major: u8) -> Result<u64, ()> {
+        match self.head()? {
+            (m, arg) if m == major => Ok(arg),
+            _ => Err(()),
+        }
+    }
+
+    /// Reads an integer.
+    pub(crate) fn int(&mut self) -> Result<i64, ()> {
+        match self.head()? {
+            (UNSIGNED, arg) => i64::try_from(arg).map_err(|_| ()),
+            (NEGATIVE, arg) => i64::try_from(arg).map(|arg| !arg).map_err(|_| ()),
+            _ => Err(()),
+        }
+    }
+
+    /// Reads a byte string.
+    pub(crate) fn bytes(&mut self) -> Result<&'a [u8], ()> {
+        let len = self.expect(BYTES)?;
+        self.take(len)
+    }
+
+    /// Reads a text string.
+    pub(crate) fn text(&mut self) -> Result<&'a str, ()> {
+        let len = self.expect(TEXT)?;
+        core::str::from_utf8(self.take(len)?).map_err(|_| ())
+    }
+
+    /// Reads the head of an array and returns its length.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn array(&mut self) -> Result<u64, ()> { self.expect(ARRAY) }
+
+    /// Reads the head of a map and returns its number of entries.
+    pub(crate) fn map(&mut self) -> Result<u64, ()> { self.expect(MAP) }
+
+    /// Skips one item including any nested items, without recursion so that deep nesting cannot
+    /// overflow the stack.
+    pub(crate) fn skip(&mut self) -> Result<(), ()> {
+        let mut pending = 1u64;
+        while pending > 0 {
+            pending -= 1;
+            let (major, arg) = self.head()?;
+            let nested = match major {
+                BYTES | TEXT => {
+                    self.take(arg)?;
+                    0
+                }
+                ARRAY => arg,
+                MAP => arg.checked_mul(2).ok_or(())?,
+                TAG => 1,
+                _ => 0,
+            };
+            pending = pending.checked_add(nested).ok_or(())?;
+            // Every pending item takes at least one byte.
+            if pending > self.data.len() as u64 {
+                return Err(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that all data has been read.
+    pub(crate) fn finish(&self) -> Result<(), ()> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], ()> {
+        let len = usize::try_from(len).map_err(|_| ())?;
+        if self.data.len() < len {
+            return Err(());
+        }
+        let (ret, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn ints() {
+        // From RFC 8949 appendix A.
+        let vectors: [(i64, &[u8]); 10] = [
+            (0, &hex!("00")),
+            (23, &hex!("17")),
+            (24, &hex!("1818")),
+            (1000, &hex!("1903e8")),
+            (1000000, &hex!("1a000f4240")),
+            (1000000000000, &hex!("1b000000e8d4a51000")),
+            (-1, &hex!("20")),
+            (-100, &hex!("3863")),
+            (-1000, &hex!("3903e7")),
+            (i64::MIN, &hex!("3b7fffffffffffffff")),
+        ];
+        for (value, encoded) in vectors {
+            let mut buf = [0u8; 9];
+            let mut encoder = SliceEncoder::new(&mut buf[..encoded.len()]);
+            encoder.int(value);
+            encoder.finish();
+            assert_eq!(&buf[..encoded.len()], encoded);
+
+            let mut reader = Reader::new(encoded);
+            assert_eq!(reader.int(), Ok(value));
+            assert_eq!(reader.finish(), Ok(()));
+        }
+        // 2^64 - 1 and -2^64 do not fit.
+        assert!(Reader::new(&hex!("1bffffffffffffffff")).int().is_err());
+        assert!(Reader::new(&hex!("3bffffffffffffffff")).int().is_err());
+    }
+
+    #[test]
+    fn skip() {
+        // [1, [2, 3], {"a": h'01', 1: 1(1.5)}, true, null], then 0.
+        let data = hex!("8501820203a26161410101c1f93e00f5f600");
+        let mut reader = Reader::new(&data);
+        reader.skip().unwrap();
+        assert_eq!(reader.int(), Ok(0));
+        assert_eq!(reader.finish(), Ok(()));
+
+        // Truncated items, indefinite lengths and huge counts are rejected.
+        assert!(Reader::new(&hex!("8301")).skip().is_err());
+        assert!(Reader::new(&hex!("4201")).skip().is_err());
+        assert!(Reader::new(&hex!("9fff")).skip().is_err());
+        assert!(Reader::new(&hex!("9bffffffffffffffff00")).skip().is_err());
+        assert!(Reader::new(&hex!("bbffffffffffffffff00")).skip().is_err());
+    }
+
+    #[test]
+    fn strings() {
+        let data = hex!("44010203046449455446f4f5");
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.bytes(), Ok(&[1, 2, 3, 4][..]));
+        assert_eq!(reader.peek_major(), Ok(TEXT));
+        assert_eq!(reader.text(), Ok("IETF"));
+        assert_eq!(reader.simple(TRUE), Ok(false));
+        assert_eq!(reader.simple(FALSE), Ok(true));
+        assert_eq!(reader.simple(TRUE), Ok(true));
+        assert_eq!(reader.finish(), Ok(()));
+        assert!(Reader::new(&hex!("62c328")).text().is_err());
+    }
+}
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! The ChaCha20 stream cipher (RFC 8439).
+
+/// The "expand 32-byte k" constant.
+const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+/// The size of a keystream block.
+const BLOCK_SIZE: usize = 64;
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// Computes the keystream block with block counter `counter`.
+fn block(key: &[u8; 32], nonce: &[u8; 12], counter: u32) -> [u8; BLOCK_SIZE] {
+    let word = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    let mut input = [0u32; 16];
+    input[..4].copy_from_slice(&SIGMA);
+    for (i, chunk) in key.chunks_exact(4).enumerate() {
+        input[4 + i] = word(chunk);
+    }
+    input[12] = counter;
+    for (i, chunk) in nonce.chunks_exact(4).enumerate() {
+        input[13 + i] = word(chunk);
+    }
+
+    let mut state = input;
+    for _ in 0..10 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+
+    let mut ret = [0u8; BLOCK_SIZE];
+    for (i, chunk) in ret.chunks_exact_mut(4).enumerate() {
+        chunk.copy_from_slice(&state[i].wrapping_add(input[i]).to_le_bytes());
+    }
+    ret
+}
+
+/// Encrypts or decrypts `data` in place, starting at block `counter`.
+///
+/// # Panics
+///
+/// If the block counter overflows, after 256 GiB of data.
+pub(crate) fn apply_keystream(key: &[u8; 32], nonce: &[u8; 12], counter: u32, data: &mut [u8]) {
+    for (i, chunk) in data.chunks_mut(BLOCK_SIZE).enumerate() {
+        let counter = u32::try_from(i)
+            .ok()
+            .and_then(|i| counter.checked_add(i))
+            .expect("ChaCha20 block counter overflow");
+        for (byte, key) in chunk.iter_mut().zip(block(key, nonce, counter).iter()) {
+            *byte ^= key;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    fn key() -> [u8; 32] {
+        let mut key = [0u8; 32];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        key
+    }
+
+    #[test]
+    fn rfc8439_block() {
+        // Section 2.3.2.
+        let nonce = hex!("000000090000004a00000000");
+        assert_eq!(
+            block(&key(), &nonce, 1),
+            hex!("10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4ed2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e")
+        );
+    }
+
+    #[test]
+    fn rfc8439_encryption() {
+        // Section 2.4.2.
+        let nonce = hex!("000000000000004a00000000");
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+        let ciphertext = hex!("6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d");
+
+        let mut data = *plaintext;
+        apply_keystream(&key(), &nonce, 1, &mut data);
+        assert_eq!(data, ciphertext);
+        apply_keystream(&key(), &nonce, 1, &mut data);
+        assert_eq!(data, *plaintext);
+    }
+}
@@ -33,6 +33,21 @@ pub const KEY_PAIR_SIZE: usize = 96;
 /// The size of a full ElligatorSwift encoding.
 pub const ELLSWIFT_ENCODING_SIZE: usize = 64;
 
+/// The size of a DER-encoded SEC1 secret key, see [`crate::SecretKey::to_sec1_der`].
+pub const SEC1_DER_SIZE: usize = 118;
+
+/// The size of a DER-encoded PKCS#8 secret key, see [`crate::SecretKey::to_pkcs8_der`].
+pub const PKCS8_DER_SIZE: usize = 135;
+
+/// The size of a DER-encoded SubjectPublicKeyInfo, see [`crate::PublicKey::to_spki_der`].
+pub const SPKI_DER_SIZE: usize = 88;
+
+/// The size of a public key as a `COSE_Key`, see [`crate::PublicKey::to_cose_key`].
+pub const COSE_PUBLIC_KEY_SIZE: usize = 75;
+
+/// The size of a secret key as a `COSE_Key`, see [`crate::SecretKey::to_cose_key`].
+pub const COSE_SECRET_KEY_SIZE: usize = 110;
+
 /// The Prime for the secp256k1 field element.
 #[rustfmt::skip]
 pub const FIELD_SIZE: [u8; 32] = [
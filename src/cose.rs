@@ -0,0 +1,614 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! CBOR Object Signing and Encryption (COSE, RFC 9052) with secp256k1 keys.
+//!
+//! This module supports `COSE_Sign1` messages signed with `ES256K` (RFC 8812), that is ECDSA
+//! over secp256k1 with SHA-256 and the 64-byte `r || s` signature encoding, and `COSE_Key`
+//! encodings of the keys of the `EC2` key type on the `secp256k1` curve.
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::alloc::vec::Vec;
+use crate::cbor::{self, Encoder as _, Reader, SliceEncoder};
+#[cfg(feature = "alloc")]
+use crate::ecdsa::{self, Signature};
+#[cfg(feature = "alloc")]
+use crate::hashes::sha256;
+#[cfg(feature = "alloc")]
+use crate::Message;
+use crate::{constants, PublicKey, SecretKey};
+
+/// The COSE algorithm identifier of `ES256K`.
+pub const ES256K: i64 = -47;
+
+/// The header parameter label of the algorithm.
+#[cfg(feature = "alloc")]
+const ALG: i64 = 1;
+/// The CBOR tag of `COSE_Sign1` messages.
+#[cfg(feature = "alloc")]
+const SIGN1_TAG: u64 = 18;
+/// The context string of the `Sig_structure` of `COSE_Sign1` messages.
+#[cfg(feature = "alloc")]
+const SIGNATURE1_CONTEXT: &str = "Signature1";
+
+/// The `COSE_Key` label of the key type.
+const KTY: i64 = 1;
+/// The `COSE_Key` label of the curve.
+const CRV: i64 = -1;
+/// The `COSE_Key` label of the x coordinate.
+const X: i64 = -2;
+/// The `COSE_Key` label of the y coordinate, or of its sign.
+const Y: i64 = -3;
+/// The `COSE_Key` label of the secret key.
+const D: i64 = -4;
+/// The `EC2` key type.
+const KTY_EC2: i64 = 2;
+/// The `secp256k1` curve.
+const CRV_SECP256K1: i64 = 8;
+
+/// Errors returned when parsing or verifying COSE structures.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum CoseError {
+    /// The data is not the expected CBOR structure.
+    MalformedCbor,
+    /// A header parameter is given more than once, or the algorithm is given by the caller.
+    DuplicateHeader(i64),
+    /// The protected header does not specify the `ES256K` algorithm.
+    UnsupportedAlgorithm,
+    /// The message has a detached payload.
+    DetachedPayload,
+    /// The key type is not `EC2`.
+    UnsupportedKeyType,
+    /// The curve is not `secp256k1`.
+    UnsupportedCurve,
+    /// The public key is missing or not a point on the curve.
+    InvalidPublicKey,
+    /// The secret key is missing, zero or not below the curve order.
+    InvalidSecretKey,
+    /// The public key does not belong to the secret key.
+    PublicKeyMismatch,
+    /// The signature is not a valid 64-byte signature.
+    InvalidSignature,
+    /// The signature does not verify.
+    IncorrectSignature,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CoseError {}
+
+impl fmt::Display for CoseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            CoseError::MalformedCbor => f.write_str("malformed COSE structure"),
+            CoseError::DuplicateHeader(label) =>
+                write!(f, "duplicate COSE header parameter {}", label),
+            CoseError::UnsupportedAlgorithm => f.write_str("unsupported COSE algorithm"),
+            CoseError::DetachedPayload => f.write_str("detached COSE payloads are not supported"),
+            CoseError::UnsupportedKeyType => f.write_str("unsupported COSE key type"),
+            CoseError::UnsupportedCurve => f.write_str("unsupported COSE curve"),
+            CoseError::InvalidPublicKey => f.write_str("invalid COSE public key"),
+            CoseError::InvalidSecretKey => f.write_str("invalid COSE secret key"),
+            CoseError::PublicKeyMismatch =>
+                f.write_str("COSE public key does not match the secret key"),
+            CoseError::InvalidSignature => f.write_str("malformed COSE signature"),
+            CoseError::IncorrectSignature => f.write_str("COSE signature does not verify"),
+        }
+    }
+}
+
+/// Maps the errors of the CBOR reader.
+fn malformed(_: ()) -> CoseError { CoseError::MalformedCbor }
+
+/// The value of a COSE header parameter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HeaderValue<'a> {
+    /// An integer.
+    Int(i64),
+    /// A byte string, such as a key identifier.
+    Bytes(&'a [u8]),
+    /// A text string, such as a content type.
+    Text(&'a str),
+}
+
+/// A `COSE_Sign1` message signed with `ES256K`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sign1 {
+    /// The serialized protected header map.
+    protected: Vec<u8>,
+    payload: Vec<u8>,
+    signature: Signature,
+}
+
+#[cfg(feature = "alloc")]
+impl Sign1 {
+    /// Signs `payload` with the secret key `sk`.
+    ///
+    /// The protected header holds the `ES256K` algorithm followed by `protected_headers`, in
+    /// order. The unprotected header is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoseError::DuplicateHeader`] if a label appears more than once in
+    /// `protected_headers` or is the algorithm label 1.
+    pub fn sign(
+        payload: &[u8],
+        protected_headers: &[(i64, HeaderValue)],
+        sk: &SecretKey,
+    ) -> Result<Sign1, CoseError> {
+        for (i, &(label, _)) in protected_headers.iter().enumerate() {
+            if label == ALG || protected_headers[..i].iter().any(|&(l, _)| l == label) {
+                return Err(CoseError::DuplicateHeader(label));
+            }
+        }
+
+        let mut protected = Vec::new();
+        protected.head(cbor::MAP, protected_headers.len() as u64 + 1);
+        protected.int(ALG);
+        protected.int(ES256K);
+        for &(label, value) in protected_headers {
+            protected.int(label);
+            match value {
+                HeaderValue::Int(value) => protected.int(value),
+                HeaderValue::Bytes(value) => protected.bytes(value),
+                HeaderValue::Text(value) => protected.text(value),
+            }
+        }
+
+        let payload = payload.to_vec();
+        let signature = ecdsa::sign(signed_message(&protected, &payload), sk);
+        Ok(Sign1 { protected, payload, signature })
+    }
+
+    /// Verifies the signature with the public key `pk`.
+    ///
+    /// Signatures with high S values are accepted, because COSE does not require low S.
+    pub fn verify(&self, pk: &PublicKey) -> Result<(), CoseError> {
+        let mut sig = self.signature;
+        sig.normalize_s();
+        sig.verify(signed_message(&self.protected, &self.payload), pk)
+            .map_err(|_| CoseError::IncorrectSignature)
+    }
+
+    /// Returns the payload.
+    #[inline]
+    pub fn payload(&self) -> &[u8] { &self.payload }
+
+    /// Returns the signature.
+    #[inline]
+    pub fn signature(&self) -> Signature { self.signature }
+
+    /// Returns the value of the protected header parameter `label`.
+    ///
+    /// Returns `None` if the parameter is absent or is not an integer, byte string or text string.
+    pub fn protected_header(&self, label: i64) -> Option<HeaderValue<'_>> {
+        let mut reader = Reader::new(&self.protected);
+        for _ in 0..reader.map().ok()? {
+            // Labels may also be text strings.
+            if reader.peek_major().ok()? == cbor::TEXT {
+                reader.text().ok()?;
+            } else if reader.int().ok()? == label {
+                return match reader.peek_major().ok()? {
+                    cbor::UNSIGNED | cbor::NEGATIVE => reader.int().ok().map(HeaderValue::Int),
+                    cbor::BYTES => reader.bytes().ok().map(HeaderValue::Bytes),
+                    cbor::TEXT => reader.text().ok().map(HeaderValue::Text),
+                    _ => None,
+                };
+            }
+            reader.skip().ok()?;
+        }
+        None
+    }
+
+    /// Serializes the message as a tagged `COSE_Sign1` structure.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut ret = Vec::with_capacity(self.protected.len() + self.payload.len() + 80);
+        ret.head(cbor::TAG, SIGN1_TAG);
+        ret.head(cbor::ARRAY, 4);
+        ret.bytes(&self.protected);
+        ret.head(cbor::MAP, 0);
+        ret.bytes(&self.payload);
+        ret.bytes(&self.signature.serialize_compact());
+        ret
+    }
+
+    /// Parses a `COSE_Sign1` structure, with or without its tag.
+    ///
+    /// The algorithm must be given in the protected header. The unprotected header is ignored.
+    pub fn from_slice(data: &[u8]) -> Result<Sign1, CoseError> {
+        let mut reader = Reader::new(data);
+        if reader.peek_major().map_err(malformed)? == cbor::TAG
+            && reader.head().map_err(malformed)? != (cbor::TAG, SIGN1_TAG)
+        {
+            return Err(CoseError::MalformedCbor);
+        }
+        if reader.array().map_err(malformed)? != 4 {
+            return Err(CoseError::MalformedCbor);
+        }
+        let protected = reader.bytes().map_err(malformed)?;
+        check_protected_header(protected)?;
+        reader.skip().map_err(malformed)?;
+        if reader.simple(cbor::NULL).map_err(malformed)? {
+            return Err(CoseError::DetachedPayload);
+        }
+        let payload = reader.bytes().map_err(malformed)?;
+        let signature = Signature::from_compact(reader.bytes().map_err(malformed)?)
+            .map_err(|_| CoseError::InvalidSignature)?;
+        reader.finish().map_err(malformed)?;
+
+        Ok(Sign1 { protected: protected.to_vec(), payload: payload.to_vec(), signature })
+    }
+}
+
+/// Checks that the serialized protected header map specifies the `ES256K` algorithm.
+#[cfg(feature = "alloc")]
+fn check_protected_header(protected: &[u8]) -> Result<(), CoseError> {
+    if protected.is_empty() {
+        return Err(CoseError::UnsupportedAlgorithm);
+    }
+    let mut reader = Reader::new(protected);
+    let mut alg = None;
+    for _ in 0..reader.map().map_err(malformed)? {
+        // Labels may also be text strings.
+        if reader.peek_major().map_err(malformed)? == cbor::TEXT {
+            reader.text().map_err(malformed)?;
+            reader.skip().map_err(malformed)?;
+            continue;
+        }
+        let label = reader.int().map_err(malformed)?;
+        if label == ALG {
+            if alg.is_some() {
+                return Err(CoseError::DuplicateHeader(ALG));
+            }
+            alg = Some(reader.int().map_err(|_| CoseError::UnsupportedAlgorithm)?);
+        } else {
+            reader.skip().map_err(malformed)?;
+        }
+    }
+    reader.finish().map_err(malformed)?;
+    match alg {
+        Some(ES256K) => Ok(()),
+        _ => Err(CoseError::UnsupportedAlgorithm),
+    }
+}
+
+/// Computes the message that is signed, the SHA-256 hash of the `Sig_structure` with empty
+/// external data.
+#[cfg(feature = "alloc")]
+fn signed_message(protected: &[u8], payload: &[u8]) -> Message {
+    let mut sig_structure = Vec::with_capacity(protected.len() + payload.len() + 24);
+    sig_structure.head(cbor::ARRAY, 4);
+    sig_structure.text(SIGNATURE1_CONTEXT);
+    sig_structure.bytes(protected);
+    sig_structure.bytes(&[]);
+    sig_structure.bytes(payload);
+    Message::from_digest(sha256::hash(&sig_structure))
+}
+
+impl PublicKey {
+    /// Serializes the public key as a `COSE_Key` map with the key type, curve and both
+    /// coordinates.
+    pub fn to_cose_key(&self) -> [u8; constants::COSE_PUBLIC_KEY_SIZE] {
+        let mut ret = [0u8; constants::COSE_PUBLIC_KEY_SIZE];
+        let mut encoder = SliceEncoder::new(&mut ret);
+        encoder.head(cbor::MAP, 4);
+        write_public_key(&mut encoder, self);
+        encoder.finish();
+        ret
+    }
+
+    /// Parses a `COSE_Key` map of an `EC2` key on the `secp256k1` curve.
+    ///
+    /// The y coordinate may be given as a boolean, the sign bit of the compressed point. Other
+    /// parameters, such as the key ID, are ignored.
+    pub fn from_cose_key(data: &[u8]) -> Result<PublicKey, CoseError> {
+        let key = CoseKey::parse(data)?;
+        key.public_key.ok_or(CoseError::InvalidPublicKey)
+    }
+}
+
+impl SecretKey {
+    /// Serializes the secret key as a `COSE_Key` map with the key type, curve, both coordinates
+    /// of the public key and the secret key.
+    ///
+    /// The returned bytes contain the secret key, treat them with the same care.
+    pub fn to_cose_key(&self) -> [u8; constants::COSE_SECRET_KEY_SIZE] {
+        let mut ret = [0u8; constants::COSE_SECRET_KEY_SIZE];
+        let mut encoder = SliceEncoder::new(&mut ret);
+        encoder.head(cbor::MAP, 5);
+        write_public_key(&mut encoder, &self.public_key());
+        encoder.int(D);
+        encoder.bytes(&self.to_secret_bytes());
+        encoder.finish();
+        ret
+    }
+
+    /// Parses a `COSE_Key` map of an `EC2` secret key on the `secp256k1` curve.
+    ///
+    /// The public key is optional but must match the secret key if present.
+    pub fn from_cose_key(data: &[u8]) -> Result<SecretKey, CoseError> {
+        let key = CoseKey::parse(data)?;
+        let sk = SecretKey::from_secret_bytes(key.secret_key.ok_or(CoseError::InvalidSecretKey)?)
+            .map_err(|_| CoseError::InvalidSecretKey)?;
+        match key.public_key {
+            Some(pk) if pk != sk.public_key() => Err(CoseError::PublicKeyMismatch),
+            _ => Ok(sk),
+        }
+    }
+}
+
+/// Writes the key type, curve and coordinates of a `COSE_Key` map.
+fn write_public_key(encoder: &mut SliceEncoder, pk: &PublicKey) {
+    let serialized = pk.serialize_uncompressed();
+    encoder.int(KTY);
+    encoder.int(KTY_EC2);
+    encoder.int(CRV);
+    encoder.int(CRV_SECP256K1);
+    encoder.int(X);
+    encoder.bytes(&serialized[1..33]);
+    encoder.int(Y);
+    encoder.bytes(&serialized[33..]);
+}
+
+/// The parameters of a parsed `COSE_Key`.
+struct CoseKey {
+    public_key: Option<PublicKey>,
+    secret_key: Option<[u8; constants::SECRET_KEY_SIZE]>,
+}
+
+impl CoseKey {
+    fn parse(data: &[u8]) -> Result<CoseKey, CoseError> {
+        let mut reader = Reader::new(data);
+        let (mut kty, mut crv) = (None, None);
+        let (mut x, mut y, mut d) = (None, None, None);
+        for _ in 0..reader.map().map_err(malformed)? {
+            // Labels may also be text strings.
+            if reader.peek_major().map_err(malformed)? == cbor::TEXT {
+                reader.text().map_err(malformed)?;
+                reader.skip().map_err(malformed)?;
+                continue;
+            }
+            let label = reader.int().map_err(malformed)?;
+            let duplicate = match label {
+                KTY => kty.replace(reader.int().map_err(malformed)?).is_some(),
+                CRV => crv.replace(reader.int().map_err(malformed)?).is_some(),
+                X => x.replace(field_element(&mut reader)?).is_some(),
+                Y => {
+                    let value = if reader.simple(cbor::FALSE).map_err(malformed)? {
+                        Err(false)
+                    } else if reader.simple(cbor::TRUE).map_err(malformed)? {
+                        Err(true)
+                    } else {
+                        Ok(field_element(&mut reader)?)
+                    };
+                    y.replace(value).is_some()
+                }
+                D => d.replace(field_element(&mut reader)?).is_some(),
+                _ => {
+                    reader.skip().map_err(malformed)?;
+                    false
+                }
+            };
+            if duplicate {
+                return Err(CoseError::DuplicateHeader(label));
+            }
+        }
+        reader.finish().map_err(malformed)?;
+
+        if kty != Some(KTY_EC2) {
+            return Err(CoseError::UnsupportedKeyType);
+        }
+        if crv != Some(CRV_SECP256K1) {
+            return Err(CoseError::UnsupportedCurve);
+        }
+        let public_key = match (x, y) {
+            (None, None) => None,
+            (Some(x), Some(Ok(y))) => {
+                let mut serialized = [0u8; constants::UNCOMPRESSED_PUBLIC_KEY_SIZE];
+                serialized[0] = 0x04;
+                serialized[1..33].copy_from_slice(&x);
+                serialized[33..].copy_from_slice(&y);
+                Some(PublicKey::from_byte_array_uncompressed(serialized))
+            }
+            // A boolean y is the sign bit, that is the parity, of the y coordinate.
+            (Some(x), Some(Err(odd))) => {
+                let mut serialized = [0u8; constants::PUBLIC_KEY_SIZE];
+                serialized[0] = if odd { 0x03 } else { 0x02 };
+                serialized[1..].copy_from_slice(&x);
+                Some(PublicKey::from_byte_array_compressed(serialized))
+            }
+            _ => return Err(CoseError::InvalidPublicKey),
+        }
+        .transpose()
+        .map_err(|_| CoseError::InvalidPublicKey)?;
+        Ok(CoseKey { public_key, secret_key: d })
+    }
+}
+
+/// Reads a 32-byte byte string.
+fn field_element(reader: &mut Reader) -> Result<[u8; 32], CoseError> {
+    reader.bytes().map_err(malformed)?.try_into().map_err(|_| CoseError::MalformedCbor)
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    fn secret_key() -> SecretKey {
+        SecretKey::from_secret_bytes(hex!(
+            "5d5d514717d3fdee3037828e5ca1df83e8fd224d344b1d043bc10642cfaa91fe"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn cose_key() {
+        let sk = secret_key();
+        let pk = sk.public_key();
+        let expected = hex!("a401022008215820f1854a93eb4fb5ab4562e16438a6e050d9b03e0bd5b3f1ac8d2048a99424c26d22582019837aead6b9fa1739bb7aad3ce5150ac47abf0fec6b51ae24b562c6977be5df");
+        assert_eq!(pk.to_cose_key(), expected);
+        assert_eq!(PublicKey::from_cose_key(&expected), Ok(pk));
+
+        let secret = sk.to_cose_key();
+        assert_eq!(secret[0], 0xa5);
+        assert_eq!(secret[1..75], expected[1..]);
+        assert_eq!(
+            secret[75..],
+            hex!("2358205d5d514717d3fdee3037828e5ca1df83e8fd224d344b1d043bc10642cfaa91fe")
+        );
+        assert_eq!(SecretKey::from_cose_key(&secret), Ok(sk));
+        assert_eq!(PublicKey::from_cose_key(&secret), Ok(pk));
+        assert_eq!(SecretKey::from_cose_key(&expected), Err(CoseError::InvalidSecretKey));
+
+        // {1: 2, -1: 8, -4: d, 2: h'6b6964', "use": "sig"}, without the public key.
+        let bare =
+            [&hex!("a501022008")[..], &secret[75..], &hex!("02436b69646375736563736967")].concat();
+        assert_eq!(SecretKey::from_cose_key(&bare), Ok(sk));
+        assert_eq!(PublicKey::from_cose_key(&bare), Err(CoseError::InvalidPublicKey));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn cose_key_compressed() {
+        let pk = secret_key().public_key();
+        // {1: 2, -1: 8, -2: x, -3: true}
+        let compressed = [&pk.to_cose_key()[..40], &hex!("22f5")].concat();
+        assert_eq!(PublicKey::from_cose_key(&compressed), Ok(pk));
+        let negated = [&pk.to_cose_key()[..40], &hex!("22f4")].concat();
+        assert_eq!(PublicKey::from_cose_key(&negated), Ok(pk.negate()));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn cose_key_invalid() {
+        let key = secret_key().to_cose_key();
+        let with = |offset: usize, data: &[u8]| {
+            let mut key = key;
+            key[offset..offset + data.len()].copy_from_slice(data);
+            key
+        };
+        // kty OKP
+        assert_eq!(SecretKey::from_cose_key(&with(2, &[0x01])), Err(CoseError::UnsupportedKeyType));
+        // crv P-256
+        assert_eq!(SecretKey::from_cose_key(&with(4, &[0x01])), Err(CoseError::UnsupportedCurve));
+        // Duplicate x.
+        assert_eq!(
+            SecretKey::from_cose_key(&with(40, &[0x21])),
+            Err(CoseError::DuplicateHeader(X))
+        );
+        // A changed y coordinate is not on the curve.
+        assert_eq!(SecretKey::from_cose_key(&with(74, &[0x00])), Err(CoseError::InvalidPublicKey));
+        // A different secret key.
+        assert_eq!(SecretKey::from_cose_key(&with(78, &[0x00])), Err(CoseError::PublicKeyMismatch));
+        assert_eq!(
+            SecretKey::from_cose_key(&with(78, &constants::ZERO)),
+            Err(CoseError::InvalidSecretKey)
+        );
+        assert_eq!(SecretKey::from_cose_key(&key[..109]), Err(CoseError::MalformedCbor));
+        assert_eq!(
+            SecretKey::from_cose_key(&[&key[..], &[0]].concat()),
+            Err(CoseError::MalformedCbor)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(secp256k1_fuzz)))]
+    fn sign1() {
+        let (sk, pk) = crate::test_random_keypair();
+        let headers = [(4, HeaderValue::Bytes(b"key-1")), (3, HeaderValue::Text("text/plain"))];
+        let msg = Sign1::sign(b"hello", &headers, &sk).unwrap();
+        assert_eq!(msg.payload(), b"hello");
+        assert_eq!(msg.protected_header(ALG), Some(HeaderValue::Int(ES256K)));
+        assert_eq!(msg.protected_header(4), Some(HeaderValue::Bytes(b"key-1")));
+        assert_eq!(msg.protected_header(3), Some(HeaderValue::Text("text/plain")));
+        assert_eq!(msg.protected_header(5), None);
+        assert_eq!(msg.verify(&pk), Ok(()));
+        assert!(msg.signature().is_low_s());
+
+        let encoded = msg.to_vec();
+        assert_eq!(encoded[..2], hex!("d284"));
+        assert_eq!(Sign1::from_slice(&encoded), Ok(msg.clone()));
+        assert_eq!(Sign1::from_slice(&encoded[1..]), Ok(msg.clone()));
+
+        let (_, other) = crate::test_random_keypair();
+        assert_eq!(msg.verify(&other), Err(CoseError::IncorrectSignature));
+        let mut changed = msg.clone();
+        changed.payload = b"hellp".to_vec();
+        assert_eq!(changed.verify(&pk), Err(CoseError::IncorrectSignature));
+        let mut high_s = msg.clone();
+        high_s.signature = msg.signature.malleate();
+        assert_eq!(high_s.verify(&pk), Ok(()));
+
+        assert_eq!(
+            Sign1::sign(b"", &[(ALG, HeaderValue::Int(-7))], &sk),
+            Err(CoseError::DuplicateHeader(ALG))
+        );
+        assert_eq!(
+            Sign1::sign(b"", &[(4, HeaderValue::Int(1)), (4, HeaderValue::Int(2))], &sk),
+            Err(CoseError::DuplicateHeader(4))
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(secp256k1_fuzz)))]
+    fn sign1_structure() {
+        use serde_cbor::Value;
+
+        let sk = secret_key();
+        let msg = Sign1::sign(b"payload", &[(4, HeaderValue::Bytes(b"11"))], &sk).unwrap();
+        // serde_cbor skips the tag.
+        let value: Value = serde_cbor::from_slice(&msg.to_vec()).unwrap();
+        let expected = Value::Array(vec![
+            Value::Bytes(hex!("a201382e04423131").to_vec()),
+            Value::Map(Default::default()),
+            Value::Bytes(b"payload".to_vec()),
+            Value::Bytes(msg.signature().serialize_compact().to_vec()),
+        ]);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    #[cfg(feature = "alloc")]
+    fn sign1_vector() {
+        // Signed with the Python `cryptography` package, with an unprotected key ID header and a
+        // high-S signature.
+        let pk = secret_key().public_key();
+        let encoded = hex!("d28444a101382ea104436b657954546869732069732074686520636f6e74656e742e5840f3345af4dff4fca851f64795f748fa1827c6d9d396705ed05a7088d5b6e04cd6ce9bed272775eb8578c6618fd868c2da5870ad9e86526ae69f63e7846310ef06");
+        let msg = Sign1::from_slice(&encoded).unwrap();
+        assert_eq!(msg.payload(), b"This is the content.");
+        assert!(!msg.signature().is_low_s());
+        assert_eq!(msg.verify(&pk), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sign1_invalid() {
+        // [h'a10126', {}, h'', h'']: ES256 instead of ES256K.
+        assert_eq!(
+            Sign1::from_slice(&hex!("8443a10126a04040")),
+            Err(CoseError::UnsupportedAlgorithm)
+        );
+        // [h'', {}, h'', h'']: no algorithm.
+        assert_eq!(Sign1::from_slice(&hex!("8440a04040")), Err(CoseError::UnsupportedAlgorithm));
+        // [h'a101382e', {}, nil, h'']: detached payload.
+        assert_eq!(Sign1::from_slice(&hex!("8444a101382ea0f640")), Err(CoseError::DetachedPayload));
+        // [h'a101382e', {}, h'', h'']: empty signature.
+        assert_eq!(
+            Sign1::from_slice(&hex!("8444a101382ea04040")),
+            Err(CoseError::InvalidSignature)
+        );
+        // 98([...]): a COSE_Sign structure.
+        assert_eq!(
+            Sign1::from_slice(&hex!("d8628444a101382ea04040")),
+            Err(CoseError::MalformedCbor)
+        );
+        assert_eq!(Sign1::from_slice(&hex!("8344a101382ea040")), Err(CoseError::MalformedCbor));
+    }
+}
@@ -9,7 +9,8 @@ use core::{ptr, str};
 use secp256k1_sys::types::{c_int, c_uchar, c_void};
 
 use crate::ffi::{self, CPtr};
-use crate::key::{PublicKey, SecretKey};
+use crate::hashes::{hkdf, sha256};
+use crate::key::{Parity, PublicKey, SecretKey, XOnlyPublicKey};
 use crate::{constants, Error};
 
 // The logic for displaying shared secrets relies on this (see `secret.rs`).
@@ -53,6 +54,16 @@ impl SharedSecret {
         SharedSecret(buf)
     }
 
+    /// Creates a new shared secret from an x-only public key and a secret key.
+    ///
+    /// The secret is the SHA-256 hash of [`shared_x_only`], so both parties derive the same value
+    /// no matter which parity their x-only public keys had as full keys. It differs from the
+    /// result of [`SharedSecret::new`], which also hashes the parity of the shared point.
+    #[inline]
+    pub fn new_xonly(point: &XOnlyPublicKey, scalar: &SecretKey) -> SharedSecret {
+        SharedSecret(sha256::hash(&shared_x_only(point, scalar)))
+    }
+
     /// Returns the shared secret as a byte value.
     #[inline]
     pub fn to_secret_bytes(&self) -> [u8; SHARED_SECRET_SIZE] { self.0 }
@@ -62,6 +73,18 @@ impl SharedSecret {
     #[inline]
     pub fn secret_bytes(&self) -> [u8; SHARED_SECRET_SIZE] { self.to_secret_bytes() }
 
+    /// Derives keying material from the shared secret with HKDF-SHA256 (RFC 5869), filling `out`.
+    ///
+    /// The shared secret is the input keying material; protocols usually pass their own `info`
+    /// string for each subkey they derive.
+    ///
+    /// # Panics
+    ///
+    /// If `out` is longer than 8160 bytes, the HKDF output limit.
+    pub fn derive_keys(&self, salt: &[u8], info: &[u8], out: &mut [u8]) {
+        hkdf::expand(&hkdf::extract(salt, &self.0), info, out)
+    }
+
     /// Creates a shared secret from `bytes` array.
     #[inline]
     pub fn from_bytes(bytes: [u8; SHARED_SECRET_SIZE]) -> SharedSecret { SharedSecret(bytes) }
@@ -145,6 +168,102 @@ pub fn shared_secret_point(point: &PublicKey, scalar: &SecretKey) -> [u8; 64] {
     xy
 }
 
+/// Computes a shared secret from a public key and a secret key, hashing the shared point with a
+/// custom hash function.
+///
+/// The hash function is called with the x and y coordinates of the shared point (32 bytes each)
+/// from within `secp256k1_ecdh`, so the point is only ever handed to it and never returned.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "rand", feature = "std"))] {
+/// # use secp256k1::{ecdh, rand};
+/// let (sk1, pk1) = secp256k1::generate_keypair(&mut rand::rng());
+/// let (sk2, pk2) = secp256k1::generate_keypair(&mut rand::rng());
+/// let x1 = ecdh::shared_secret_with_hasher(&pk2, &sk1, |x, _y| x);
+/// let x2 = ecdh::shared_secret_with_hasher(&pk1, &sk2, |x, _y| x);
+/// assert_eq!(x1, x2);
+/// # }
+/// ```
+pub fn shared_secret_with_hasher<F, T>(point: &PublicKey, scalar: &SecretKey, hash_function: F) -> T
+where
+    F: FnMut([u8; 32], [u8; 32]) -> T,
+{
+    // The callback stores its result here; the C output buffer is unused.
+    let mut data = (hash_function, None);
+    let mut unused = [0u8; 32];
+
+    let res = unsafe {
+        ffi::secp256k1_ecdh(
+            ffi::secp256k1_context_no_precomp,
+            unused.as_mut_ptr(),
+            point.as_c_ptr(),
+            scalar.as_c_ptr(),
+            Some(c_callback_hasher::<F, T>),
+            &mut data as *mut (F, Option<T>) as *mut c_void,
+        )
+    };
+    // Our callback *always* returns 1.
+    debug_assert_eq!(res, 1);
+    data.1.expect("the hash callback is always called")
+}
+
+unsafe extern "C" fn c_callback_hasher<F, T>(
+    _output: *mut c_uchar,
+    x: *const c_uchar,
+    y: *const c_uchar,
+    data: *mut c_void,
+) -> c_int
+where
+    F: FnMut([u8; 32], [u8; 32]) -> T,
+{
+    let data = &mut *(data as *mut (F, Option<T>));
+    let mut x_array = [0u8; 32];
+    let mut y_array = [0u8; 32];
+    ptr::copy_nonoverlapping(x, x_array.as_mut_ptr(), 32);
+    ptr::copy_nonoverlapping(y, y_array.as_mut_ptr(), 32);
+    data.1 = Some((data.0)(x_array, y_array));
+    1
+}
+
+/// Computes the x coordinate of the shared point from an x-only public key and a secret key.
+///
+/// **Important: use of a strong cryptographic hash function may be critical to security! Do NOT use
+/// unless you understand cryptographical implications.** If not, use
+/// [`SharedSecret::new_xonly`] instead.
+///
+/// The x coordinate is the same for both possible parities of `point`, so it equals the first
+/// 32 bytes of [`shared_secret_point`] for the full public key, whichever parity it has. This is
+/// the ECDH output used by Nostr NIP-04 and NIP-44.
+pub fn shared_x_only(point: &XOnlyPublicKey, scalar: &SecretKey) -> [u8; 32] {
+    let mut x = [0u8; 32];
+
+    let res = unsafe {
+        ffi::secp256k1_ecdh(
+            ffi::secp256k1_context_no_precomp,
+            x.as_mut_ptr(),
+            point.public_key(Parity::Even).as_c_ptr(),
+            scalar.as_c_ptr(),
+            Some(c_callback_x_only),
+            ptr::null_mut(),
+        )
+    };
+    // Our callback *always* returns 1.
+    debug_assert_eq!(res, 1);
+    x
+}
+
+unsafe extern "C" fn c_callback_x_only(
+    output: *mut c_uchar,
+    x: *const c_uchar,
+    _y: *const c_uchar,
+    _data: *mut c_void,
+) -> c_int {
+    ptr::copy_nonoverlapping(x, output, 32);
+    1
+}
+
 unsafe extern "C" fn c_callback(
     output: *mut c_uchar,
     x: *const c_uchar,
@@ -204,6 +323,70 @@ mod tests {
         assert!(sec_odd != sec2);
     }
 
+    #[test]
+    fn derive_keys() {
+        // The salt, info and length of RFC 5869 test case 1, with a 32-byte input key since shared
+        // secrets are always 32 bytes. Computed with HKDF from Python's `cryptography` package.
+        let salt = hex_lit::hex!("000102030405060708090a0b0c");
+        let info = hex_lit::hex!("f0f1f2f3f4f5f6f7f8f9");
+        let secret = SharedSecret::from_bytes([0x0b; 32]);
+        let mut out = [0u8; 42];
+        secret.derive_keys(&salt, &info, &mut out);
+        assert_eq!(
+            out,
+            hex_lit::hex!("d4100799f26a09615a72af3e58fa3841a2ff20d5ace3fb392e562e207fe6b718581eea4341652d405fe5")
+        );
+
+        let mut other = [0u8; 42];
+        secret.derive_keys(&salt, b"other", &mut other);
+        assert_ne!(out, other);
+    }
+
+    #[test]
+    fn ecdh_with_hasher() {
+        use crate::hashes::sha256;
+
+        let (sk1, pk1) = crate::test_random_keypair();
+        let (sk2, pk2) = crate::test_random_keypair();
+
+        let point = super::shared_secret_point(&pk2, &sk1);
+        let xy = super::shared_secret_with_hasher(&pk2, &sk1, |x, y| (x, y));
+        assert_eq!(xy.0, point[..32]);
+        assert_eq!(xy.1, point[32..]);
+
+        // Reproduces the default hash function of `SharedSecret::new`.
+        let hash = |x: [u8; 32], y: [u8; 32]| {
+            let mut compressed = [0u8; 33];
+            compressed[0] = 0x02 | (y[31] & 1);
+            compressed[1..].copy_from_slice(&x);
+            SharedSecret::from_bytes(sha256::hash(&compressed))
+        };
+        let sec1 = super::shared_secret_with_hasher(&pk2, &sk1, hash);
+        assert_eq!(sec1, SharedSecret::new(&pk2, &sk1));
+        assert_eq!(super::shared_secret_with_hasher(&pk1, &sk2, hash), sec1);
+    }
+
+    #[test]
+    fn ecdh_xonly() {
+        use crate::key::Parity;
+
+        let (sk1, pk1) = crate::test_random_keypair();
+        let (sk2, pk2) = crate::test_random_keypair();
+        let (xonly1, _) = pk1.x_only_public_key();
+        let (xonly2, _) = pk2.x_only_public_key();
+
+        let x = super::shared_x_only(&xonly2, &sk1);
+        assert_eq!(super::shared_x_only(&xonly1, &sk2), x);
+        for parity in [Parity::Even, Parity::Odd] {
+            let point = super::shared_secret_point(&xonly2.public_key(parity), &sk1);
+            assert_eq!(point[..32], x);
+        }
+
+        let sec1 = SharedSecret::new_xonly(&xonly2, &sk1);
+        assert_eq!(SharedSecret::new_xonly(&xonly1, &sk2), sec1);
+        assert_eq!(sec1.to_secret_bytes(), crate::hashes::sha256::hash(&x));
+    }
+
     #[test]
     fn test_c_callback() {
         let x = [5u8; 32];
@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Sign-to-contract and the anti-exfil protocol for ECDSA.
+//!
+//! A signer that chooses its own nonces can leak its secret key through them, for example a
+//! compromised hardware wallet. With the anti-exfil protocol the host contributes randomness to
+//! the nonce and afterwards checks that the signer actually used it:
+//!
+//! 1. The host draws 32 random bytes of `host_data` and sends [`host_commit`] of them to the
+//!    signer.
+//! 2. The signer replies with [`signer_commit`], an [`Opening`] that fixes its nonce before it
+//!    learns `host_data`.
+//! 3. The host sends `host_data` and the signer creates the signature with [`sign`].
+//! 4. The host checks the signature with [`host_verify`].
+//!
+//! This follows the `ecdsa_s2c` module of libsecp256k1-zkp, using the same hash tags and RFC6979
+//! nonce derivation.
+//!
+//! ```
+//! # #[cfg(all(feature = "rand", feature = "std", not(secp256k1_fuzz)))] {
+//! use secp256k1::ecdsa::anti_exfil;
+//! use secp256k1::{rand, Message};
+//!
+//! let (sk, pk) = secp256k1::generate_keypair(&mut rand::rng());
+//! let msg = Message::from_digest([0xab; 32]);
+//!
+//! // Host
+//! let host_data = rand::random::<[u8; 32]>();
+//! let host_commitment = anti_exfil::host_commit(&host_data);
+//! // Signer
+//! let opening = anti_exfil::signer_commit(msg, &sk, &host_commitment);
+//! // Signer, after receiving the host data
+//! let sig = anti_exfil::sign(msg, &sk, &host_data);
+//! // Host
+//! assert!(anti_exfil::host_verify(&sig, msg, &pk, &host_data, &opening).is_ok());
+//! # }
+//! ```
+
+use core::ptr;
+
+use crate::ecdsa::Signature;
+use crate::ffi::types::c_void;
+use crate::ffi::{self, CPtr};
+use crate::hashes::sha256;
+use crate::{constants, Error, Message, PublicKey, Scalar, SecretKey};
+
+/// The tag of the hash that commits to the host data.
+const DATA_TAG: &[u8] = b"s2c/ecdsa/data";
+/// The tag of the hash that tweaks the nonce with the host data.
+const POINT_TAG: &[u8] = b"s2c/ecdsa/point";
+
+/// The signer's original public nonce, which opens a sign-to-contract commitment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Opening(PublicKey);
+
+impl Opening {
+    /// Parses an opening from its 33-byte serialization.
+    pub fn from_byte_array(data: &[u8; constants::PUBLIC_KEY_SIZE]) -> Result<Opening, Error> {
+        PublicKey::from_byte_array_compressed(*data).map(Opening)
+    }
+
+    /// Serializes the opening as a 33-byte compressed point.
+    pub fn serialize(&self) -> [u8; constants::PUBLIC_KEY_SIZE] { self.0.serialize() }
+}
+
+/// Commits to the host's random data, step 1 of the protocol.
+pub fn host_commit(host_data: &[u8; 32]) -> [u8; 32] {
+    let mut engine = sha256::tagged_engine(DATA_TAG);
+    engine.input(host_data);
+    engine.finalize()
+}
+
+/// Returns the signer's original nonce point for `msg`, step 2 of the protocol.
+///
+/// The nonce is derived deterministically from `msg`, `sk` and `host_commitment`, so calling
+/// [`sign`] later with the data behind `host_commitment` uses the same nonce.
+pub fn signer_commit(
+    msg: impl Into<Message>,
+    sk: &SecretKey,
+    host_commitment: &[u8; 32],
+) -> Opening {
+    Opening(original_nonce(&msg.into(), sk, host_commitment).1)
+}
+
+/// Signs `msg` with a nonce tweaked by the host's data, step 3 of the protocol.
+pub fn sign(msg: impl Into<Message>, sk: &SecretKey, host_data: &[u8; 32]) -> Signature {
+    s2c_sign(msg, sk, host_data).0
+}
+
+/// Checks that `sig` is a valid signature of `pk` on `msg` whose nonce commits to `host_data`
+/// under `opening`, step 4 of the protocol.
+///
+/// # Errors
+///
+/// Returns [`Error::IncorrectSignature`] if the signature is invalid or the signer did not use
+/// the host's data.
+pub fn host_verify(
+    sig: &Signature,
+    msg: impl Into<Message>,
+    pk: &PublicKey,
+    host_data: &[u8; 32],
+    opening: &Opening,
+) -> Result<(), Error> {
+    if !s2c_verify_commit(sig, host_data, opening) {
+        return Err(Error::IncorrectSignature);
+    }
+    super::verify(sig, msg, pk)
+}
+
+/// Signs `msg`, committing to `data` in the nonce.
+///
+/// Returns the signature and the opening needed to prove the commitment with
+/// [`s2c_verify_commit`].
+pub fn s2c_sign(msg: impl Into<Message>, sk: &SecretKey, data: &[u8; 32]) -> (Signature, Opening) {
+    let msg = msg.into();
+    // The nonce is derived from a commitment to the data, so that a signer can compute its
+    // opening from the commitment alone, see `signer_commit`.
+    let (nonce, original) = original_nonce(&msg, sk, &host_commit(data));
+    // A tweak that cancels the nonce is too unlikely to ever happen.
+    let tweaked = nonce.add_tweak(&commit_tweak(&original, data)).expect("tweaked nonce is valid");
+
+    // The nonce is computed up front because the nonce function cannot use the global context.
+    // A valid nonce is only rejected if r or s end up zero, which never happens in practice.
+    let sig = super::sign_with_nonce_fn(msg, sk, |_, _, _, attempt| {
+        if attempt == 0 {
+            Some(tweaked.to_secret_bytes())
+        } else {
+            None
+        }
+    })
+    .expect("valid nonce produces a signature");
+    (sig, Opening(original))
+}
+
+/// Checks that the nonce of `sig` commits to `data` under `opening`.
+///
+/// This does not verify the signature itself.
+pub fn s2c_verify_commit(sig: &Signature, data: &[u8; 32], opening: &Opening) -> bool {
+    let commitment = match opening.0.add_exp_tweak(&commit_tweak(&opening.0, data)) {
+        Ok(commitment) => commitment,
+        Err(_) => return false,
+    };
+    let mut x = [0u8; 32];
+    x.copy_from_slice(&commitment.serialize()[1..]);
+    let mut r = [0u8; 32];
+    r.copy_from_slice(&sig.serialize_compact()[..32]);
+
+    // r is the x coordinate of the nonce point reduced modulo the curve order.
+    x == r || (x >= constants::CURVE_ORDER && x == add_curve_order(&r))
+}
+
+/// Computes the tweak `H(ser33(original) || data)` that commits `original` to `data`.
+fn commit_tweak(original: &PublicKey, data: &[u8; 32]) -> Scalar {
+    let mut engine = sha256::tagged_engine(POINT_TAG);
+    engine.input(&original.serialize());
+    engine.input(data);
+    // A hash output above the curve order is too unlikely to ever happen.
+    Scalar::from_be_bytes(engine.finalize()).expect("hash is below the curve order")
+}
+
+/// Derives the signer's nonce from `msg`, `sk` and `ndata` with RFC6979, the same way
+/// `secp256k1_ecdsa_sign` does, and returns it together with its point.
+fn original_nonce(msg: &Message, sk: &SecretKey, ndata: &[u8; 32]) -> (SecretKey, PublicKey) {
+    let key32 = sk.to_secret_bytes();
+    let nonce_fn = unsafe { ffi::secp256k1_nonce_function_rfc6979 }.expect("rfc6979 is non-null");
+    let mut nonce = [0u8; 32];
+    let mut attempt = 0;
+    loop {
+        let ret = unsafe {
+            nonce_fn(
+                nonce.as_mut_c_ptr(),
+                msg.as_c_ptr(),
+                key32.as_c_ptr(),
+                ptr::null(),
+                ndata.as_c_ptr() as *mut c_void,
+                attempt,
+            )
+        };
+        if ret == 1 {
+            if let Ok(nonce) = SecretKey::from_secret_bytes(nonce) {
+                return (nonce, PublicKey::from_secret_key(&nonce));
+            }
+        }
+        attempt += 1;
+    }
+}
+
+/// Computes `r + n` for an `r` small enough that the sum fits in 32 bytes.
+fn add_curve_order(r: &[u8; 32]) -> [u8; 32] {
+    let mut ret = [0u8; 32];
+    let mut carry = 0u16;
+    for i in (0..32).rev() {
+        let sum = u16::from(r[i]) + u16::from(constants::CURVE_ORDER[i]) + carry;
+        ret[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn anti_exfil_protocol() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = Message::from_digest(crate::test_random_32_bytes());
+        let host_data = crate::test_random_32_bytes();
+
+        let host_commitment = host_commit(&host_data);
+        let opening = signer_commit(msg, &sk, &host_commitment);
+        let sig = sign(msg, &sk, &host_data);
+        assert_eq!(host_verify(&sig, msg, &pk, &host_data, &opening), Ok(()));
+
+        // The signature is a regular ECDSA signature.
+        assert_eq!(crate::ecdsa::verify(&sig, msg, &pk), Ok(()));
+        assert_eq!(s2c_sign(msg, &sk, &host_data), (sig, opening));
+
+        let other_data = crate::test_random_32_bytes();
+        assert_eq!(
+            host_verify(&sig, msg, &pk, &other_data, &opening),
+            Err(Error::IncorrectSignature)
+        );
+        // A signer that ignores the host data is caught.
+        let plain = crate::ecdsa::sign(msg, &sk);
+        assert_eq!(
+            host_verify(&plain, msg, &pk, &host_data, &opening),
+            Err(Error::IncorrectSignature)
+        );
+        let (_, other_pk) = crate::test_random_keypair();
+        assert_eq!(
+            host_verify(&sig, msg, &other_pk, &host_data, &opening),
+            Err(Error::IncorrectSignature)
+        );
+    }
+
+    #[test]
+    fn opening_serialization() {
+        let (_, pk) = crate::test_random_keypair();
+        let opening = Opening(pk);
+        assert_eq!(Opening::from_byte_array(&opening.serialize()), Ok(opening));
+        assert_eq!(Opening::from_byte_array(&[0; 33]), Err(Error::InvalidPublicKey));
+    }
+
+    #[test]
+    fn curve_order_addition() {
+        let mut r = [0u8; 32];
+        r[31] = 1;
+        let mut expected = constants::CURVE_ORDER;
+        expected[31] += 1;
+        assert_eq!(add_curve_order(&r), expected);
+    }
+}
@@ -0,0 +1,297 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Strict [BIP-66] DER parsing of ECDSA signatures.
+//!
+//! [BIP-66]: https://github.com/bitcoin/bips/blob/master/bip-0066.mediawiki
+
+use core::fmt;
+
+use super::Signature;
+
+/// The shortest valid DER signature, with one byte integers.
+const MIN_LEN: usize = 8;
+/// The longest valid DER signature, with 33 byte integers.
+const MAX_LEN: usize = 72;
+
+/// Tag of a DER sequence.
+const SEQUENCE: u8 = 0x30;
+/// Tag of a DER integer.
+const INTEGER: u8 = 0x02;
+
+/// The reason a signature was rejected by [`Signature::from_der_strict`].
+///
+/// Except for the total length errors, every variant carries the offset of the offending byte
+/// in the input.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum DerError {
+    /// The input is shorter than any valid signature.
+    TooShort(usize),
+    /// The input is longer than any valid signature.
+    TooLong(usize),
+    /// The byte at `offset` is not the tag the encoding requires.
+    UnexpectedTag {
+        /// Offset of the tag byte.
+        offset: usize,
+        /// The expected tag.
+        expected: u8,
+        /// The tag that was found.
+        found: u8,
+    },
+    /// The length byte at `offset` does not match the length of the input.
+    LengthMismatch {
+        /// Offset of the length byte.
+        offset: usize,
+    },
+    /// The integer at `offset` has length zero.
+    EmptyInteger {
+        /// Offset of the integer's length byte.
+        offset: usize,
+    },
+    /// The integer starting at `offset` is negative.
+    NegativeInteger {
+        /// Offset of the integer's first byte.
+        offset: usize,
+    },
+    /// The integer starting at `offset` has an unnecessary leading zero byte.
+    ExcessPadding {
+        /// Offset of the integer's first byte.
+        offset: usize,
+    },
+    /// The encoding is valid but `s` is in the upper half of the curve order.
+    ///
+    /// Such signatures are valid under BIP-66 but non-standard under the low-S rule of
+    /// [BIP-146]. Use [`Signature::from_der`] to parse them anyway.
+    ///
+    /// [BIP-146]: https://github.com/bitcoin/bips/blob/master/bip-0146.mediawiki
+    HighS {
+        /// Offset of the first byte of `s`.
+        offset: usize,
+    },
+}
+
+impl DerError {
+    /// Returns the offset of the offending byte, or the length of the input for
+    /// [`DerError::TooShort`] and [`DerError::TooLong`].
+    pub fn offset(&self) -> usize {
+        match *self {
+            DerError::TooShort(len) | DerError::TooLong(len) => len,
+            DerError::UnexpectedTag { offset, .. }
+            | DerError::LengthMismatch { offset }
+            | DerError::EmptyInteger { offset }
+            | DerError::NegativeInteger { offset }
+            | DerError::ExcessPadding { offset }
+            | DerError::HighS { offset } => offset,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DerError {}
+
+impl fmt::Display for DerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            DerError::TooShort(len) => write!(f, "DER signature too short ({} bytes)", len),
+            DerError::TooLong(len) => write!(f, "DER signature too long ({} bytes)", len),
+            DerError::UnexpectedTag { offset, expected, found } => write!(
+                f,
+                "expected DER tag {:#04x} at offset {}, found {:#04x}",
+                expected, offset, found
+            ),
+            DerError::LengthMismatch { offset } =>
+                write!(f, "DER length at offset {} does not match the data", offset),
+            DerError::EmptyInteger { offset } =>
+                write!(f, "zero-length DER integer at offset {}", offset),
+            DerError::NegativeInteger { offset } =>
+                write!(f, "negative DER integer at offset {}", offset),
+            DerError::ExcessPadding { offset } =>
+                write!(f, "DER integer at offset {} has excess padding", offset),
+            DerError::HighS { offset } => write!(
+                f,
+                "s value at offset {} is not in the lower half of the curve order",
+                offset
+            ),
+        }
+    }
+}
+
+impl Signature {
+    /// Parses a DER-encoded signature, enforcing the [BIP-66] encoding rules exactly and
+    /// rejecting signatures with a high `s` value.
+    ///
+    /// Unlike [`Signature::from_der`], this reports the reason and position of a failure. Values
+    /// of `r` or `s` that are not below the curve order are accepted, like BIP-66 does; such
+    /// signatures never verify.
+    ///
+    /// [BIP-66]: https://github.com/bitcoin/bips/blob/master/bip-0066.mediawiki
+    pub fn from_der_strict(data: &[u8]) -> Result<Signature, DerError> {
+        if data.len() < MIN_LEN {
+            return Err(DerError::TooShort(data.len()));
+        }
+        if data.len() > MAX_LEN {
+            return Err(DerError::TooLong(data.len()));
+        }
+        check_bip66(data)?;
+
+        let sig = Signature::from_der(data).expect("BIP-66 encodings are valid DER");
+        if !sig.is_low_s() {
+            let len_r = usize::from(data[3]);
+            return Err(DerError::HighS { offset: len_r + 6 });
+        }
+        Ok(sig)
+    }
+
+    /// Parses a DER-encoded signature followed by a sighash byte, as found in Bitcoin scripts,
+    /// with the same rules as [`Signature::from_der_strict`].
+    ///
+    /// Returns the signature and the sighash byte, which is not validated.
+    pub fn from_der_strict_with_sighash(data: &[u8]) -> Result<(Signature, u8), DerError> {
+        match data.split_last() {
+            Some((&sighash, der)) if !der.is_empty() =>
+                Signature::from_der_strict(der).map(|sig| (sig, sighash)),
+            _ => Err(DerError::TooShort(data.len())),
+        }
+    }
+}
+
+/// Checks the structure of a DER signature without sighash byte, following
+/// `IsValidSignatureEncoding` in Bitcoin Core. `data` must be 8 to 72 bytes long.
+fn check_bip66(data: &[u8]) -> Result<(), DerError> {
+    let len = data.len();
+
+    if data[0] != SEQUENCE {
+        return Err(DerError::UnexpectedTag { offset: 0, expected: SEQUENCE, found: data[0] });
+    }
+    if usize::from(data[1]) != len - 2 {
+        return Err(DerError::LengthMismatch { offset: 1 });
+    }
+
+    let len_r = usize::from(data[3]);
+    if 5 + len_r >= len {
+        return Err(DerError::LengthMismatch { offset: 3 });
+    }
+    let len_s = usize::from(data[5 + len_r]);
+    if len_r + len_s + 6 != len {
+        return Err(DerError::LengthMismatch { offset: 5 + len_r });
+    }
+
+    check_integer(data, 2, len_r)?;
+    check_integer(data, len_r + 4, len_s)
+}
+
+/// Checks the DER integer whose tag is at `offset` and whose content is `len` bytes long.
+fn check_integer(data: &[u8], offset: usize, len: usize) -> Result<(), DerError> {
+    if data[offset] != INTEGER {
+        return Err(DerError::UnexpectedTag { offset, expected: INTEGER, found: data[offset] });
+    }
+    if len == 0 {
+        return Err(DerError::EmptyInteger { offset: offset + 1 });
+    }
+    let start = offset + 2;
+    if data[start] & 0x80 != 0 {
+        return Err(DerError::NegativeInteger { offset: start });
+    }
+    if len > 1 && data[start] == 0x00 && data[start + 1] & 0x80 == 0 {
+        return Err(DerError::ExcessPadding { offset: start });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    /// A low-S signature with a 33 byte r and a 32 byte s.
+    fn valid_der() -> [u8; 71] {
+        hex!("3045022100c8d3e2a3a2c52aba2d4e0ba3fc3cc5e42dfd2da5d3bd3a4bbb9fd2c86a75c57c02203da4e9a4b0ee33ffd9aa8a6d3b8bd0ff3f5b5ab82f7fb4a0a05ffd31e41cbf81")
+    }
+
+    #[test]
+    fn valid() {
+        let sig = Signature::from_der_strict(&valid_der()).unwrap();
+        assert_eq!(sig, Signature::from_der(&valid_der()).unwrap());
+
+        let mut with_sighash = [0u8; 72];
+        with_sighash[..71].copy_from_slice(&valid_der());
+        with_sighash[71] = 0x01;
+        assert_eq!(Signature::from_der_strict_with_sighash(&with_sighash), Ok((sig, 0x01)));
+        assert_eq!(
+            Signature::from_der_strict_with_sighash(&valid_der()),
+            Err(DerError::LengthMismatch { offset: 1 })
+        );
+        assert_eq!(Signature::from_der_strict_with_sighash(&[]), Err(DerError::TooShort(0)));
+    }
+
+    #[test]
+    fn invalid() {
+        let check = |mutate: fn(&mut [u8; 71]), err: DerError| {
+            let mut data = valid_der();
+            mutate(&mut data);
+            assert_eq!(Signature::from_der_strict(&data), Err(err));
+        };
+
+        check(|d| d[0] = 0x31, DerError::UnexpectedTag { offset: 0, expected: 0x30, found: 0x31 });
+        check(|d| d[1] = 0x44, DerError::LengthMismatch { offset: 1 });
+        check(|d| d[3] = 0x42, DerError::LengthMismatch { offset: 3 });
+        check(|d| d[3] = 0x20, DerError::LengthMismatch { offset: 37 });
+        check(|d| d[38] = 0x21, DerError::LengthMismatch { offset: 38 });
+        check(|d| d[2] = 0x03, DerError::UnexpectedTag { offset: 2, expected: 0x02, found: 0x03 });
+        check(
+            |d| d[37] = 0x04,
+            DerError::UnexpectedTag { offset: 37, expected: 0x02, found: 0x04 },
+        );
+        // r without its sign padding is negative.
+        check(|d| d[4] = 0x80, DerError::NegativeInteger { offset: 4 });
+        check(|d| d[39] = 0xbd, DerError::NegativeInteger { offset: 39 });
+        // A zero padding byte in front of a positive value.
+        check(|d| d[5] = 0x48, DerError::ExcessPadding { offset: 4 });
+
+        assert_eq!(Signature::from_der_strict(&valid_der()[..7]), Err(DerError::TooShort(7)));
+        assert_eq!(Signature::from_der_strict(&[0x30; 73]), Err(DerError::TooLong(73)));
+        // Both integers empty: the shortest input with the right outer length.
+        assert_eq!(
+            Signature::from_der_strict(&hex!("3006020002000000")),
+            Err(DerError::LengthMismatch { offset: 5 })
+        );
+        assert_eq!(
+            Signature::from_der_strict(&hex!("300602000201010000")),
+            Err(DerError::LengthMismatch { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn empty_integer() {
+        assert_eq!(
+            Signature::from_der_strict(&hex!("3006020002020101")),
+            Err(DerError::EmptyInteger { offset: 3 })
+        );
+        assert_eq!(
+            Signature::from_der_strict(&hex!("3006020201010200")),
+            Err(DerError::EmptyInteger { offset: 7 })
+        );
+    }
+
+    #[test]
+    fn high_s() {
+        let mut sig = Signature::from_der_strict(&valid_der()).unwrap();
+        // Negating s makes it high.
+        let mut compact = sig.serialize_compact();
+        let mut borrow = 0i16;
+        for i in (32..64).rev() {
+            let d =
+                i16::from(crate::constants::CURVE_ORDER[i - 32]) - i16::from(compact[i]) - borrow;
+            compact[i] = d.rem_euclid(256) as u8;
+            borrow = i16::from(d < 0);
+        }
+        sig = Signature::from_compact(&compact).unwrap();
+        let der = sig.serialize_der();
+        let len_r = usize::from(der[3]);
+        assert_eq!(Signature::from_der_strict(&der), Err(DerError::HighS { offset: len_r + 6 }));
+        assert_eq!(DerError::HighS { offset: 39 }.offset(), 39);
+        assert!(Signature::from_der(&der).is_ok());
+    }
+}
@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Ethereum-style recoverable signatures.
+//!
+//! Ethereum serializes a [`RecoverableSignature`] as the 65 bytes `r || s || v`, where `v`
+//! encodes the [`RecoveryId`] in one of several ways, see [`VEncoding`]. Signed messages are
+//! hashed with [`keccak256`] and public keys are identified by their
+//! [`PublicKey::to_eth_address`].
+
+use crate::ecdsa::{RecoverableSignature, RecoveryId};
+use crate::{Error, Message, PublicKey};
+
+/// Computes the Keccak-256 hash of `data`.
+///
+/// This is the hash function Ethereum uses for transactions, messages and addresses. Note that
+/// it is not the same as the standardized SHA3-256.
+pub fn keccak256(data: &[u8]) -> [u8; 32] { crate::hashes::keccak256::hash(data) }
+
+/// How the [`RecoveryId`] is encoded into the `v` value of an `r || s || v` signature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VEncoding {
+    /// `v` is the recovery ID itself, 0 or 1.
+    Raw,
+    /// `v` is 27 or 28, as used by pre-[EIP-155] transactions, `eth_sign` and `ecrecover`.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    Legacy,
+    /// `v` is `chain_id * 2 + 35` or `chain_id * 2 + 36`, as used by [EIP-155] transactions.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    Eip155(u64),
+}
+
+impl VEncoding {
+    /// Returns the `v` value of recovery ID zero.
+    fn base(self) -> Option<u64> {
+        match self {
+            VEncoding::Raw => Some(0),
+            VEncoding::Legacy => Some(27),
+            VEncoding::Eip155(chain_id) => chain_id.checked_mul(2)?.checked_add(35),
+        }
+    }
+
+    /// Encodes `recid` as a `v` value.
+    ///
+    /// # Errors
+    ///
+    /// If `recid` is not 0 or 1, which Ethereum cannot represent, or if the EIP-155 chain ID
+    /// is so large that `v` does not fit in a `u64`.
+    pub fn encode(self, recid: RecoveryId) -> Result<u64, Error> {
+        match recid {
+            RecoveryId::Zero | RecoveryId::One => self
+                .base()
+                .and_then(|base| base.checked_add(u64::from(recid.to_u8())))
+                .ok_or(Error::InvalidRecoveryId),
+            RecoveryId::Two | RecoveryId::Three => Err(Error::InvalidRecoveryId),
+        }
+    }
+
+    /// Decodes a `v` value into a recovery ID.
+    ///
+    /// # Errors
+    ///
+    /// If `v` is not one of the two values of this encoding.
+    pub fn decode(self, v: u64) -> Result<RecoveryId, Error> {
+        match self.base().and_then(|base| v.checked_sub(base)) {
+            Some(0) => Ok(RecoveryId::Zero),
+            Some(1) => Ok(RecoveryId::One),
+            _ => Err(Error::InvalidRecoveryId),
+        }
+    }
+}
+
+impl RecoverableSignature {
+    /// Serializes the signature as `r || s || v`, with `v` encoded as `encoding`.
+    ///
+    /// # Errors
+    ///
+    /// If the recovery ID is not 0 or 1, or if `v` does not fit in a single byte, which is the
+    /// case for most EIP-155 chain IDs above 109. Use [`RecoverableSignature::serialize_compact`]
+    /// and [`VEncoding::encode`] to handle those.
+    pub fn to_rsv(&self, encoding: VEncoding) -> Result<[u8; 65], Error> {
+        let (recid, compact) = self.serialize_compact();
+        let v = u8::try_from(encoding.encode(recid)?).map_err(|_| Error::InvalidRecoveryId)?;
+        let mut ret = [0u8; 65];
+        ret[..64].copy_from_slice(&compact);
+        ret[64] = v;
+        Ok(ret)
+    }
+
+    /// Parses an `r || s || v` signature, with `v` encoded as `encoding`.
+    ///
+    /// # Errors
+    ///
+    /// If `v` is not valid for `encoding` or if `r` or `s` are out of range.
+    pub fn from_rsv(rsv: &[u8; 65], encoding: VEncoding) -> Result<RecoverableSignature, Error> {
+        let recid = encoding.decode(u64::from(rsv[64]))?;
+        RecoverableSignature::from_compact(&rsv[..64], recid)
+    }
+}
+
+/// Recovers the address that signed `msg_hash`, like Ethereum's `ecrecover` precompile.
+///
+/// `v` may use either the [`VEncoding::Raw`] or the [`VEncoding::Legacy`] encoding. As required
+/// by [EIP-2], signatures with an `s` value in the upper half of the curve order are rejected.
+///
+/// # Errors
+///
+/// If `v` is invalid, if `r` or `s` are out of range, if `s` is high or if no public key can be
+/// recovered.
+///
+/// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+pub fn ecrecover(msg_hash: [u8; 32], rsv: &[u8; 65]) -> Result<[u8; 20], Error> {
+    let sig = RecoverableSignature::from_rsv(rsv, VEncoding::Legacy)
+        .or_else(|_| RecoverableSignature::from_rsv(rsv, VEncoding::Raw))?;
+
+    if !sig.to_standard().is_low_s() {
+        return Err(Error::InvalidSignature);
+    }
+
+    let pk: PublicKey = sig.recover_ecdsa(Message::from_digest(msg_hash))?;
+    Ok(pk.to_eth_address())
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+    use crate::{constants, SecretKey};
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn eth_address() {
+        let sk = SecretKey::from_secret_bytes(constants::ONE).unwrap();
+        assert_eq!(
+            PublicKey::from_secret_key(&sk).to_eth_address(),
+            hex!("7e5f4552091a69125d5dfcb7b8c2659029395bdf"),
+        );
+    }
+
+    #[test]
+    fn v_encoding() {
+        assert_eq!(VEncoding::Raw.encode(RecoveryId::One), Ok(1));
+        assert_eq!(VEncoding::Legacy.encode(RecoveryId::Zero), Ok(27));
+        assert_eq!(VEncoding::Eip155(1).encode(RecoveryId::Zero), Ok(37));
+        assert_eq!(VEncoding::Eip155(137).encode(RecoveryId::One), Ok(310));
+        assert_eq!(VEncoding::Legacy.encode(RecoveryId::Two), Err(Error::InvalidRecoveryId));
+        assert_eq!(
+            VEncoding::Eip155(u64::MAX).encode(RecoveryId::Zero),
+            Err(Error::InvalidRecoveryId)
+        );
+
+        assert_eq!(VEncoding::Legacy.decode(28), Ok(RecoveryId::One));
+        assert_eq!(VEncoding::Eip155(1).decode(38), Ok(RecoveryId::One));
+        assert_eq!(VEncoding::Legacy.decode(1), Err(Error::InvalidRecoveryId));
+        assert_eq!(VEncoding::Raw.decode(2), Err(Error::InvalidRecoveryId));
+        assert_eq!(VEncoding::Eip155(1).decode(27), Err(Error::InvalidRecoveryId));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn eip155_example() {
+        // The example transaction from EIP-155.
+        let sk = SecretKey::from_secret_bytes([0x46; 32]).unwrap();
+        let signing_hash = hex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+        let expected = hex!("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa63627667cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d8325");
+
+        let sig =
+            RecoverableSignature::sign_ecdsa_recoverable(Message::from_digest(signing_hash), &sk);
+        let rsv = sig.to_rsv(VEncoding::Eip155(1)).unwrap();
+        assert_eq!(rsv, expected);
+        assert_eq!(RecoverableSignature::from_rsv(&rsv, VEncoding::Eip155(1)), Ok(sig));
+        assert_eq!(
+            RecoverableSignature::from_rsv(&rsv, VEncoding::Legacy),
+            Err(Error::InvalidRecoveryId)
+        );
+        assert_eq!(sig.to_rsv(VEncoding::Eip155(111)), Err(Error::InvalidRecoveryId));
+
+        let address = hex!("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f");
+        assert_eq!(PublicKey::from_secret_key(&sk).to_eth_address(), address);
+        for encoding in [VEncoding::Raw, VEncoding::Legacy] {
+            let rsv = sig.to_rsv(encoding).unwrap();
+            assert_eq!(ecrecover(signing_hash, &rsv), Ok(address));
+        }
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn ecrecover_rejects_high_s() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg_hash = keccak256(b"high s");
+        let sig = RecoverableSignature::sign_ecdsa_recoverable(Message::from_digest(msg_hash), &sk);
+        let mut rsv = sig.to_rsv(VEncoding::Legacy).unwrap();
+        assert_eq!(ecrecover(msg_hash, &rsv), Ok(pk.to_eth_address()));
+
+        // Replace s with n - s and flip the recovery ID, which recovers the same key.
+        let high_s = negate(rsv[32..64].try_into().unwrap());
+        rsv[32..64].copy_from_slice(&high_s);
+        rsv[64] = if rsv[64] == 27 { 28 } else { 27 };
+        assert_eq!(ecrecover(msg_hash, &rsv), Err(Error::InvalidSignature));
+
+        rsv[64] = 29;
+        assert_eq!(ecrecover(msg_hash, &rsv), Err(Error::InvalidRecoveryId));
+    }
+
+    /// Computes `n - s` for a big-endian scalar `s`.
+    fn negate(s: [u8; 32]) -> [u8; 32] {
+        let mut ret = [0u8; 32];
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let mut d = i16::from(constants::CURVE_ORDER[i]) - i16::from(s[i]) - borrow;
+            borrow = if d < 0 { 1 } else { 0 };
+            if d < 0 {
+                d += 256;
+            }
+            ret[i] = d as u8;
+        }
+        ret
+    }
+}
@@ -0,0 +1,491 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Compact JSON Web Signatures (RFC 7515) with the secp256k1 algorithms of RFC 8812.
+//!
+//! A compact JWS is the string `header.payload.signature`, each part base64url-encoded. The
+//! signature covers the SHA-256 hash of `header.payload` and is the 64-byte `r || s` encoding of
+//! an ECDSA signature for `ES256K`. `ES256K-R`, used by several DID methods, appends the
+//! recovery ID as a 65th byte so that the public key can be recovered from the token.
+//!
+//! Signatures are always produced with low S and signatures with high S are rejected, so that
+//! every token has a single valid signature.
+
+use core::fmt;
+
+use crate::alloc::string::String;
+use crate::alloc::vec;
+use crate::alloc::vec::Vec;
+#[cfg(feature = "recovery")]
+use crate::ecdsa::RecoverableSignature;
+use crate::ecdsa::{self, Signature};
+use crate::hashes::sha256;
+use crate::{base64, Message, PublicKey, SecretKey};
+
+/// The encoded protected header `{"alg":"ES256K"}`.
+const ES256K_HEADER: &str = "eyJhbGciOiJFUzI1NksifQ";
+/// The encoded protected header `{"alg":"ES256K-R"}`.
+#[cfg(feature = "recovery")]
+const ES256K_R_HEADER: &str = "eyJhbGciOiJFUzI1NkstUiJ9";
+
+/// A JWS signature algorithm on secp256k1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Algorithm {
+    /// ECDSA with SHA-256 and a 64-byte `r || s` signature.
+    Es256k,
+    /// ECDSA with SHA-256 and a 65-byte `r || s || v` signature, `v` being the recovery ID.
+    #[cfg(feature = "recovery")]
+    Es256kR,
+}
+
+impl Algorithm {
+    /// Returns the name of the algorithm in the JWS `alg` header parameter.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Es256k => "ES256K",
+            #[cfg(feature = "recovery")]
+            Algorithm::Es256kR => "ES256K-R",
+        }
+    }
+
+    /// Returns the length of the signature of the algorithm.
+    fn signature_len(self) -> usize {
+        match self {
+            Algorithm::Es256k => 64,
+            #[cfg(feature = "recovery")]
+            Algorithm::Es256kR => 65,
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.name()) }
+}
+
+/// Errors returned when parsing or verifying a compact JWS.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum JwsError {
+    /// The JWS does not consist of three dot-separated parts.
+    Malformed,
+    /// A part of the JWS is not valid unpadded base64url.
+    InvalidBase64,
+    /// The protected header is not a JSON object with an `alg` string.
+    InvalidHeader,
+    /// The header names an algorithm other than `ES256K` and `ES256K-R`, or one that is not
+    /// accepted by the called function.
+    UnsupportedAlgorithm,
+    /// The header lists critical extensions, none of which are supported.
+    UnsupportedCriticalHeader,
+    /// The signature has the wrong length or is not a valid signature encoding.
+    InvalidSignature,
+    /// The signature has a high S value.
+    HighS,
+    /// The signature does not verify.
+    IncorrectSignature,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JwsError {}
+
+impl fmt::Display for JwsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            JwsError::Malformed => f.write_str("JWS must have three dot-separated parts"),
+            JwsError::InvalidBase64 => f.write_str("invalid base64url in JWS"),
+            JwsError::InvalidHeader => f.write_str("invalid JWS header"),
+            JwsError::UnsupportedAlgorithm => f.write_str("unsupported JWS algorithm"),
+            JwsError::UnsupportedCriticalHeader =>
+                f.write_str("JWS header contains unsupported critical parameters"),
+            JwsError::InvalidSignature => f.write_str("malformed JWS signature"),
+            JwsError::HighS => f.write_str("JWS signature has a high S value"),
+            JwsError::IncorrectSignature => f.write_str("JWS signature does not verify"),
+        }
+    }
+}
+
+/// Signs `payload` with `ES256K`, returning the compact JWS.
+pub fn sign(payload: &[u8], sk: &SecretKey) -> String {
+    let mut jws = signing_input(ES256K_HEADER, payload);
+    let sig = ecdsa::sign(message(&jws), sk);
+    jws.push('.');
+    push_base64url(&mut jws, &sig.serialize_compact());
+    jws
+}
+
+/// Signs `payload` with `ES256K-R`, returning the compact JWS.
+#[cfg(feature = "recovery")]
+pub fn sign_recoverable(payload: &[u8], sk: &SecretKey) -> String {
+    let mut jws = signing_input(ES256K_R_HEADER, payload);
+    let sig = RecoverableSignature::sign_ecdsa_recoverable(message(&jws), sk);
+    jws.push('.');
+    push_base64url(&mut jws, &sig.to_byte_array());
+    jws
+}
+
+/// Verifies a compact JWS signed by `pk` and returns its decoded payload.
+///
+/// Both `ES256K` and, with the `recovery` feature, `ES256K-R` tokens are accepted.
+pub fn verify(jws: &str, pk: &PublicKey) -> Result<Vec<u8>, JwsError> {
+    let parts = Parts::parse(jws)?;
+    let sig = match parts.alg {
+        Algorithm::Es256k => Signature::from_compact(&parts.signature),
+        #[cfg(feature = "recovery")]
+        Algorithm::Es256kR => recoverable_signature(&parts.signature).map(|sig| sig.to_standard()),
+    }
+    .map_err(|_| JwsError::InvalidSignature)?;
+    if !sig.is_low_s() {
+        return Err(JwsError::HighS);
+    }
+    sig.verify(message(parts.signing_input), pk).map_err(|_| JwsError::IncorrectSignature)?;
+    Ok(parts.payload)
+}
+
+/// Recovers the signer of an `ES256K-R` compact JWS, returning its public key and the decoded
+/// payload.
+///
+/// Any signature recovers to some public key, so the caller must check that the key is one it
+/// trusts.
+#[cfg(feature = "recovery")]
+pub fn recover(jws: &str) -> Result<(PublicKey, Vec<u8>), JwsError> {
+    let parts = Parts::parse(jws)?;
+    if parts.alg != Algorithm::Es256kR {
+        return Err(JwsError::UnsupportedAlgorithm);
+    }
+    let sig = recoverable_signature(&parts.signature).map_err(|_| JwsError::InvalidSignature)?;
+    if !sig.to_standard().is_low_s() {
+        return Err(JwsError::HighS);
+    }
+    let pk = sig
+        .recover_ecdsa(message(parts.signing_input))
+        .map_err(|_| JwsError::IncorrectSignature)?;
+    Ok((pk, parts.payload))
+}
+
+/// Returns the algorithm named in the protected header of a compact JWS.
+pub fn algorithm(jws: &str) -> Result<Algorithm, JwsError> {
+    let header = jws.split('.').next().unwrap_or_default();
+    parse_header(&decode_base64url(header)?)
+}
+
+#[cfg(feature = "recovery")]
+fn recoverable_signature(data: &[u8]) -> Result<RecoverableSignature, crate::Error> {
+    let data = data.try_into().map_err(|_| crate::Error::InvalidSignature)?;
+    RecoverableSignature::from_byte_array(data)
+}
+
+/// The decoded parts of a compact JWS.
+struct Parts<'a> {
+    alg: Algorithm,
+    signing_input: &'a str,
+    payload: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl<'a> Parts<'a> {
+    fn parse(jws: &'a str) -> Result<Self, JwsError> {
+        let mut iter = jws.split('.');
+        let (header, payload, signature) =
+            match (iter.next(), iter.next(), iter.next(), iter.next()) {
+                (Some(header), Some(payload), Some(signature), None) =>
+                    (header, payload, signature),
+                _ => return Err(JwsError::Malformed),
+            };
+
+        let alg = parse_header(&decode_base64url(header)?)?;
+        let signature = decode_base64url(signature)?;
+        if signature.len() != alg.signature_len() {
+            return Err(JwsError::InvalidSignature);
+        }
+        Ok(Parts {
+            alg,
+            signing_input: &jws[..header.len() + 1 + payload.len()],
+            payload: decode_base64url(payload)?,
+            signature,
+        })
+    }
+}
+
+/// Returns the encoded header and payload, joined by a dot.
+///
+/// The string has room for the signature to be appended.
+fn signing_input(header: &str, payload: &[u8]) -> String {
+    let len = header.len()
+        + 1
+        + base64::url_safe_encoded_len(payload.len())
+        + 1
+        + base64::url_safe_encoded_len(65);
+    let mut jws = String::with_capacity(len);
+    jws.push_str(header);
+    jws.push('.');
+    push_base64url(&mut jws, payload);
+    jws
+}
+
+/// Computes the message that is signed for the JWS signing input.
+fn message(signing_input: &str) -> Message {
+    Message::from_digest(sha256::hash(signing_input.as_bytes()))
+}
+
+fn push_base64url(s: &mut String, data: &[u8]) {
+    let mut buf = vec![0u8; base64::url_safe_encoded_len(data.len())];
+    s.push_str(base64::encode_url_safe(data, &mut buf).expect("buffer is large enough"));
+}
+
+fn decode_base64url(s: &str) -> Result<Vec<u8>, JwsError> {
+    let mut buf = vec![0u8; s.len() * 3 / 4];
+    let len = base64::decode_url_safe(s, &mut buf).map_err(|_| JwsError::InvalidBase64)?;
+    buf.truncate(len);
+    Ok(buf)
+}
+
+/// Parses the JSON protected header and returns its algorithm.
+///
+/// Members other than `alg` and `crit` are skipped without further validation.
+fn parse_header(header: &[u8]) -> Result<Algorithm, JwsError> {
+    let mut json = Json { data: header, pos: 0 };
+    let alg = json.alg().map_err(|_| JwsError::InvalidHeader)?;
+    match alg {
+        Header::Alg(b"ES256K") => Ok(Algorithm::Es256k),
+        #[cfg(feature = "recovery")]
+        Header::Alg(b"ES256K-R") => Ok(Algorithm::Es256kR),
+        Header::Alg(_) => Err(JwsError::UnsupportedAlgorithm),
+        Header::Critical => Err(JwsError::UnsupportedCriticalHeader),
+    }
+}
+
+/// The outcome of scanning a JWS header.
+enum Header<'a> {
+    /// The raw value of the `alg` member.
+    Alg(&'a [u8]),
+    /// The header has a `crit` member.
+    Critical,
+}
+
+/// A minimal scanner for the flat JSON object of a JWS header.
+struct Json<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Json<'a> {
+    fn alg(&mut self) -> Result<Header<'a>, ()> {
+        let mut alg = None;
+        self.expect(b'{')?;
+        if self.peek()? == b'}' {
+            self.pos += 1;
+        } else {
+            loop {
+                let key = self.string()?;
+                self.expect(b':')?;
+                match key {
+                    b"alg" if alg.is_some() => return Err(()),
+                    b"alg" => alg = Some(self.string()?),
+                    b"crit" => return Ok(Header::Critical),
+                    _ => self.skip_value()?,
+                }
+                match self.next()? {
+                    b',' => continue,
+                    b'}' => break,
+                    _ => return Err(()),
+                }
+            }
+        }
+        self.skip_whitespace();
+        if self.pos != self.data.len() {
+            return Err(());
+        }
+        alg.map(Header::Alg).ok_or(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.data.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    /// Returns the next non-whitespace byte without consuming it.
+    fn peek(&mut self) -> Result<u8, ()> {
+        self.skip_whitespace();
+        self.data.get(self.pos).copied().ok_or(())
+    }
+
+    fn next(&mut self) -> Result<u8, ()> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ()> {
+        if self.next()? == byte {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Consumes a string and returns its raw contents, with escape sequences left in place.
+    fn string(&mut self) -> Result<&'a [u8], ()> {
+        self.expect(b'"')?;
+        let start = self.pos;
+        loop {
+            match *self.data.get(self.pos).ok_or(())? {
+                b'"' => break,
+                b'\\' => self.pos += 2,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        Ok(&self.data[start..self.pos - 1])
+    }
+
+    /// Consumes a value of any type, without recursion so that deep nesting cannot overflow the
+    /// stack.
+    fn skip_value(&mut self) -> Result<(), ()> {
+        let mut depth = 0usize;
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.string()?;
+                }
+                b'{' | b'[' => {
+                    self.pos += 1;
+                    depth += 1;
+                }
+                b'}' | b']' if depth > 0 => {
+                    self.pos += 1;
+                    depth -= 1;
+                }
+                b',' | b':' if depth > 0 => self.pos += 1,
+                b',' | b':' | b'}' | b']' => return Err(()),
+                _ => {
+                    let start = self.pos;
+                    while let Some(b'a'..=b'z' | b'0'..=b'9' | b'-' | b'+' | b'.' | b'E') =
+                        self.data.get(self.pos)
+                    {
+                        self.pos += 1;
+                    }
+                    if self.pos == start {
+                        return Err(());
+                    }
+                }
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+    use crate::alloc::string::ToString;
+
+    #[test]
+    fn parse_header() {
+        let vectors: [(&[u8], Result<Algorithm, JwsError>); 10] = [
+            (br#"{"alg":"ES256K"}"#, Ok(Algorithm::Es256k)),
+            (
+                br#" { "typ" : "JWT", "kid": "did:example:123#key-1", "alg": "ES256K" } "#,
+                Ok(Algorithm::Es256k),
+            ),
+            (
+                br#"{"x5c":["a","b"],"jwk":{"kty":"EC","n":[1,2.5e3,true,null]},"alg":"ES256K"}"#,
+                Ok(Algorithm::Es256k),
+            ),
+            (br#"{"alg":"ES256"}"#, Err(JwsError::UnsupportedAlgorithm)),
+            (
+                br#"{"alg":"ES256K","crit":["b64"],"b64":false}"#,
+                Err(JwsError::UnsupportedCriticalHeader),
+            ),
+            (br#"{"alg":"ES256K","alg":"ES256K"}"#, Err(JwsError::InvalidHeader)),
+            (br#"{"typ":"JWT"}"#, Err(JwsError::InvalidHeader)),
+            (br#"{"alg":"ES256K""#, Err(JwsError::InvalidHeader)),
+            (br#"{"alg":"ES256K"}x"#, Err(JwsError::InvalidHeader)),
+            (br#"{"alg":"ES256K","typ":}"#, Err(JwsError::InvalidHeader)),
+        ];
+        for (header, expected) in vectors {
+            assert_eq!(super::parse_header(header), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn sign_verify() {
+        let (sk, pk) = crate::test_random_keypair();
+        let payload = br#"{"iss":"joe","exp":1300819380}"#;
+
+        let jws = sign(payload, &sk);
+        assert!(jws.starts_with("eyJhbGciOiJFUzI1NksifQ."));
+        assert_eq!(algorithm(&jws), Ok(Algorithm::Es256k));
+        assert_eq!(verify(&jws, &pk).as_deref(), Ok(&payload[..]));
+
+        let (_, other) = crate::test_random_keypair();
+        assert_eq!(verify(&jws, &other), Err(JwsError::IncorrectSignature));
+
+        // A token with a changed payload.
+        let mut parts: Vec<&str> = jws.split('.').collect();
+        parts[1] = "e30";
+        assert_eq!(verify(&parts.join("."), &pk), Err(JwsError::IncorrectSignature));
+
+        // The same token with a high-S signature.
+        let sig = Signature::from_compact(&decode_base64url(parts[2]).unwrap()).unwrap();
+        let mut high_s = String::new();
+        push_base64url(&mut high_s, &sig.malleate().serialize_compact());
+        let jws = jws.replace(parts[2], &high_s);
+        assert_eq!(verify(&jws, &pk), Err(JwsError::HighS));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn vector() {
+        // Produced with the Python `cryptography` package, its DER signature converted to r || s
+        // and normalized to low S.
+        let pk = PublicKey::from_slice(&hex_lit::hex!(
+            "03f1854a93eb4fb5ab4562e16438a6e050d9b03e0bd5b3f1ac8d2048a99424c26d"
+        ))
+        .unwrap();
+        let jws = "eyJ0eXAiOiJKV1QiLCJhbGciOiJFUzI1NksifQ.eyJzdWIiOiIxMjM0NTY3ODkwIn0.\
+            Au_oVSnFzGjrJY6Er4wurpLebXCwCMJDf-oPNmgbsysJXHkiwEKf-mh2TFXp18-9RdwyB8B5vSzGhJp62Xp7qQ";
+        assert_eq!(verify(jws, &pk).unwrap(), br#"{"sub":"1234567890"}"#);
+    }
+
+    #[test]
+    fn malformed() {
+        let (_, pk) = crate::test_random_keypair();
+        assert_eq!(verify("", &pk), Err(JwsError::Malformed));
+        assert_eq!(verify("a.b", &pk), Err(JwsError::Malformed));
+        assert_eq!(verify("a.b.c.d", &pk), Err(JwsError::Malformed));
+        assert_eq!(verify("eyJhbGciOiJFUzI1NksifQ==.e30.", &pk), Err(JwsError::InvalidBase64));
+        // {"alg":"none"}
+        assert_eq!(verify("eyJhbGciOiJub25lIn0.e30.", &pk), Err(JwsError::UnsupportedAlgorithm));
+        assert_eq!(verify("eyJhbGciOiJFUzI1NksifQ.e30.AAAA", &pk), Err(JwsError::InvalidSignature));
+        assert_eq!(algorithm("e30.e30."), Err(JwsError::InvalidHeader));
+        assert_eq!(JwsError::HighS.to_string(), "JWS signature has a high S value");
+    }
+
+    #[test]
+    #[cfg(all(feature = "recovery", not(secp256k1_fuzz)))]
+    fn sign_recoverable_recover() {
+        let (sk, pk) = crate::test_random_keypair();
+        let payload = b"hello";
+
+        let jws = sign_recoverable(payload, &sk);
+        assert!(jws.starts_with("eyJhbGciOiJFUzI1NkstUiJ9."));
+        assert_eq!(algorithm(&jws), Ok(Algorithm::Es256kR));
+        assert_eq!(recover(&jws), Ok((pk, payload.to_vec())));
+        assert_eq!(verify(&jws, &pk), Ok(payload.to_vec()));
+
+        let es256k = sign(payload, &sk);
+        assert_eq!(recover(&es256k), Err(JwsError::UnsupportedAlgorithm));
+
+        // Recovery IDs above 3 are invalid.
+        let mut sig = decode_base64url(jws.rsplit('.').next().unwrap()).unwrap();
+        sig[64] = 4;
+        let mut bad = jws[..jws.rfind('.').unwrap() + 1].to_string();
+        push_base64url(&mut bad, &sig);
+        assert_eq!(recover(&bad), Err(JwsError::InvalidSignature));
+    }
+}
@@ -3,15 +3,29 @@
 //! Structs and functionality related to the ECDSA signature algorithm.
 //!
 
+pub mod anti_exfil;
+mod der;
+#[cfg(feature = "recovery")]
+pub mod eth;
+#[cfg(feature = "alloc")]
+pub mod jws;
 #[cfg(feature = "recovery")]
 mod recovery;
 pub mod serialized_signature;
+#[cfg(feature = "recovery")]
+pub mod signed_message;
 
 use core::{fmt, ptr, str};
 
+pub use self::der::DerError;
+#[cfg(all(feature = "recovery", feature = "serde"))]
+pub use self::recovery::serde_compact_2098;
 #[cfg(feature = "recovery")]
 pub use self::recovery::{RecoverableSignature, RecoveryId};
 pub use self::serialized_signature::SerializedSignature;
+#[cfg(feature = "alloc")]
+use crate::alloc::vec::Vec;
+use crate::ffi::types::{c_int, c_uchar, c_uint, c_void};
 use crate::ffi::CPtr;
 use crate::{ecdsa, ffi, from_hex, Error, Message, PublicKey, Secp256k1, SecretKey};
 
@@ -141,6 +155,55 @@ impl Signature {
         }
     }
 
+    /// Returns whether `s` is in the lower half of the curve order, the form produced by
+    /// [`Signature::normalize_s`] and required by [`verify`].
+    pub fn is_low_s(&self) -> bool {
+        unsafe {
+            // A null output only reports whether the signature needs normalizing.
+            ffi::secp256k1_ecdsa_signature_normalize(
+                ffi::secp256k1_context_no_precomp,
+                ptr::null_mut(),
+                self.as_c_ptr(),
+            ) == 0
+        }
+    }
+
+    /// Returns whether `r` is in the lower half of its range, so that it serializes to at most
+    /// 32 bytes in DER, the form produced by [`sign_low_r`].
+    pub fn is_low_r(&self) -> bool { compact_sig_has_zero_first_bit(&self.0) }
+
+    /// Returns the length of the DER serialization of the signature, without serializing it.
+    ///
+    /// This is the same as `self.serialize_der().len()`, between 8 and 72 bytes.
+    pub fn der_len(&self) -> usize {
+        // A DER integer is the minimal big-endian encoding plus a sign padding byte if the top
+        // bit is set.
+        fn integer_len(bytes: &[u8]) -> usize {
+            let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1);
+            bytes.len() - start + usize::from(bytes[start] & 0x80 != 0)
+        }
+        let compact = self.serialize_compact();
+        // Sequence tag and length, plus a tag and length byte for each integer.
+        6 + integer_len(&compact[..32]) + integer_len(&compact[32..])
+    }
+
+    /// Returns the other valid signature for the same message and key, with `s` replaced by
+    /// `n - s`.
+    ///
+    /// The twin of a low-S signature is a high-S signature and vice versa. This is the
+    /// malleability that [`Signature::normalize_s`] removes.
+    pub fn malleate(&self) -> Signature {
+        let mut compact = self.serialize_compact();
+        unsafe {
+            // This fails for a zero s, but leaves it zero, which is its own negation.
+            ffi::secp256k1_ec_seckey_negate(
+                ffi::secp256k1_context_no_precomp,
+                compact[32..].as_mut_c_ptr(),
+            );
+        }
+        Signature::from_compact(&compact).expect("negated s is in range")
+    }
+
     #[inline]
     /// Serializes the signature in DER format
     pub fn serialize_der(&self) -> SerializedSignature {
@@ -276,48 +339,255 @@ pub fn sign_with_noncedata(
     sign_ecdsa_with_noncedata_pointer(msg, sk, Some(noncedata))
 }
 
+/// The user's nonce function, passed to [`nonce_callback`] through the `data` pointer.
+struct NonceFnData<F> {
+    nonce_fn: F,
+    /// The payload of a panic in `nonce_fn`, to be resumed once libsecp256k1 has returned.
+    #[cfg(feature = "std")]
+    panic: Option<std::boxed::Box<dyn std::any::Any + Send>>,
+}
+
+/// Aborts the process if dropped during unwinding, by panicking while already panicking.
+#[cfg(not(feature = "std"))]
+struct AbortOnUnwind;
+
+#[cfg(not(feature = "std"))]
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) { panic!("the nonce function panicked inside a libsecp256k1 callback") }
+}
+
+unsafe extern "C" fn nonce_callback<F>(
+    nonce32: *mut c_uchar,
+    msg32: *const c_uchar,
+    key32: *const c_uchar,
+    algo16: *const c_uchar,
+    data: *mut c_void,
+    attempt: c_uint,
+) -> c_int
+where
+    F: FnMut(&[u8; 32], &[u8; 32], Option<&[u8; 16]>, u32) -> Option<[u8; 32]>,
+{
+    let data: &mut NonceFnData<F> = &mut *(data as *mut NonceFnData<F>);
+    // Borrow the inputs in place, so that no copies of the secret key are left on the stack.
+    let msg = &*(msg32 as *const [u8; 32]);
+    let key = &*(key32 as *const [u8; 32]);
+    let algo = if algo16.is_null() { None } else { Some(&*(algo16 as *const [u8; 16])) };
+
+    // Unwinding out of an `extern "C"` function is undefined behavior, so a panic is caught and
+    // signing aborted, or the process is aborted if the panic cannot be caught.
+    #[cfg(feature = "std")]
+    let nonce = {
+        let nonce_fn = &mut data.nonce_fn;
+        let call = std::panic::AssertUnwindSafe(|| nonce_fn(msg, key, algo, attempt));
+        match std::panic::catch_unwind(call) {
+            Ok(nonce) => nonce,
+            Err(payload) => {
+                data.panic = Some(payload);
+                None
+            }
+        }
+    };
+    #[cfg(not(feature = "std"))]
+    let nonce = {
+        let guard = AbortOnUnwind;
+        let nonce = (data.nonce_fn)(msg, key, algo, attempt);
+        core::mem::forget(guard);
+        nonce
+    };
+
+    match nonce {
+        Some(nonce) => {
+            ptr::copy_nonoverlapping(nonce.as_ptr(), nonce32, nonce.len());
+            1
+        }
+        None => 0,
+    }
+}
+
+/// Constructs a signature for `msg` using the secret key `sk` and a nonce produced by
+/// `nonce_fn`.
+///
+/// `nonce_fn` is called with the message, the secret key, the algorithm tag (always `None` for
+/// ECDSA) and an attempt counter. If the returned nonce is zero or not below the curve order, it
+/// is called again with the next attempt number, so it must not keep returning the same value.
+/// Returning `None` aborts signing, in which case this function returns `None` as well.
+///
+/// **Warning**: reusing a nonce for two different messages, or using a nonce that is predictable
+/// to anyone else, leaks the secret key. Prefer [`sign`] unless a protocol requires control over
+/// the nonce.
+///
+/// `nonce_fn` runs inside libsecp256k1 while the global context is borrowed, so it should not call
+/// functions of this crate that sign with the global context; with the `std` feature they panic.
+///
+/// # Panics
+///
+/// If `nonce_fn` panics, the panic is resumed once libsecp256k1 has returned. Without the `std`
+/// feature a panic cannot be caught at the C boundary, so the process is aborted instead.
+///
+/// Requires a signing-capable context.
+pub fn sign_with_nonce_fn<F>(
+    msg: impl Into<Message>,
+    sk: &SecretKey,
+    nonce_fn: F,
+) -> Option<Signature>
+where
+    F: FnMut(&[u8; 32], &[u8; 32], Option<&[u8; 16]>, u32) -> Option<[u8; 32]>,
+{
+    let msg = msg.into();
+    let noncefp = nonce_callback::<F>;
+    let mut data = NonceFnData {
+        nonce_fn,
+        #[cfg(feature = "std")]
+        panic: None,
+    };
+    unsafe {
+        let mut ret = ffi::Signature::new();
+        let res = crate::with_global_context(
+            |secp: &Secp256k1<crate::AllPreallocated>| {
+                ffi::secp256k1_ecdsa_sign(
+                    secp.ctx.as_ptr(),
+                    &mut ret,
+                    msg.as_c_ptr(),
+                    sk.as_c_ptr(),
+                    Some(noncefp),
+                    &mut data as *mut NonceFnData<F> as *const c_void,
+                )
+            },
+            Some(&sk.to_secret_bytes()),
+        );
+        #[cfg(feature = "std")]
+        if let Some(payload) = data.panic.take() {
+            std::panic::resume_unwind(payload);
+        }
+
+        if res == 1 {
+            Some(Signature::from(ret))
+        } else {
+            None
+        }
+    }
+}
+
 fn sign_grind_with_check(
     msg: impl Into<Message>,
     sk: &SecretKey,
     check: impl Fn(&ffi::Signature) -> bool,
 ) -> Signature {
-    let mut entropy_p: *const ffi::types::c_void = ptr::null();
-    let mut counter: u32 = 0;
-    let mut extra_entropy = [0u8; 32];
     let msg = msg.into();
+    let mut counter: u32 = 0;
     loop {
-        unsafe {
-            let mut ret = ffi::Signature::new();
-            // We can assume the return value because it's not possible to construct
-            // an invalid signature from a valid `Message` and `SecretKey`
-            let res = crate::with_global_context(
-                |secp: &Secp256k1<crate::AllPreallocated>| {
-                    ffi::secp256k1_ecdsa_sign(
-                        secp.ctx.as_ptr(),
-                        &mut ret,
-                        msg.as_c_ptr(),
-                        sk.as_c_ptr(),
-                        ffi::secp256k1_nonce_function_rfc6979,
-                        entropy_p,
-                    )
-                },
-                Some(&sk.to_secret_bytes()),
-            );
-            assert_eq!(res, 1);
+        let sig = sign_with_grind_counter(&msg, sk, counter);
+        if check(&sig.0) {
+            return sig;
+        }
+        counter += 1;
 
-            if check(&ret) {
-                return Signature::from(ret);
-            }
+        // When fuzzing, these checks will usually spinloop forever, so just short-circuit them.
+        #[cfg(secp256k1_fuzz)]
+        return sig;
+    }
+}
 
-            counter += 1;
-            extra_entropy[..4].copy_from_slice(&counter.to_le_bytes());
-            entropy_p = extra_entropy.as_c_ptr().cast::<ffi::types::c_void>();
+/// Signs `msg` with the RFC6979 nonce for grinding attempt `counter`.
+///
+/// Like Bitcoin Core, the first attempt uses no extra entropy and later attempts pass the
+/// counter as 32 bytes of extra entropy, little-endian in the first four bytes.
+fn sign_with_grind_counter(msg: &Message, sk: &SecretKey, counter: u32) -> Signature {
+    let mut extra_entropy = [0u8; 32];
+    let entropy_p: *const ffi::types::c_void = if counter == 0 {
+        ptr::null()
+    } else {
+        extra_entropy[..4].copy_from_slice(&counter.to_le_bytes());
+        extra_entropy.as_c_ptr().cast::<ffi::types::c_void>()
+    };
+    unsafe {
+        let mut ret = ffi::Signature::new();
+        // We can assume the return value because it's not possible to construct
+        // an invalid signature from a valid `Message` and `SecretKey`
+        let res = crate::with_global_context(
+            |secp: &Secp256k1<crate::AllPreallocated>| {
+                ffi::secp256k1_ecdsa_sign(
+                    secp.ctx.as_ptr(),
+                    &mut ret,
+                    msg.as_c_ptr(),
+                    sk.as_c_ptr(),
+                    ffi::secp256k1_nonce_function_rfc6979,
+                    entropy_p,
+                )
+            },
+            Some(&sk.to_secret_bytes()),
+        );
+        assert_eq!(res, 1);
+        Signature::from(ret)
+    }
+}
 
-            // When fuzzing, these checks will usually spinloop forever, so just short-circuit them.
-            #[cfg(secp256k1_fuzz)]
-            return Signature::from(ret);
+/// The goal and the budget of [`sign_grind`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GrindTarget {
+    /// Grinding stops at the first signature whose DER encoding is at most this long.
+    pub max_der_len: usize,
+    /// Whether the signature must also have a low `r`, see [`Signature::is_low_r`].
+    ///
+    /// A 70-byte signature does not necessarily have a low `r`, since a high `r` can be offset
+    /// by a short `s`, so this is needed to grind like [`sign_low_r`] and Bitcoin Core do.
+    pub low_r: bool,
+    /// The maximum number of signing operations. At least one is always performed.
+    pub max_attempts: u32,
+}
+
+impl GrindTarget {
+    /// Grinds for a low `r` within `max_attempts` signing operations.
+    ///
+    /// If the budget suffices, [`sign_grind`] returns the same signature as [`sign_low_r`].
+    pub fn low_r(max_attempts: u32) -> GrindTarget {
+        GrindTarget { max_der_len: 70, low_r: true, max_attempts }
+    }
+
+    /// Returns whether `sig` meets this target.
+    fn is_met_by(&self, sig: &Signature) -> bool {
+        sig.der_len() <= self.max_der_len && (!self.low_r || sig.is_low_r())
+    }
+}
+
+/// Constructs a signature for `msg` using the secret key `sk` and RFC6979 nonces, grinding the
+/// nonce until a signature meets `target` or `target.max_attempts` signatures have been created.
+///
+/// Returns the first signature meeting the target or, if there is none, the shortest signature
+/// found, the earliest one among equally short signatures. The second value is the number of
+/// signing operations performed. The nonces are derived the same way as in
+/// [`sign_low_r`] and Bitcoin Core, so the same inputs always produce the same result.
+///
+/// ```rust
+/// # #[cfg(all(feature = "rand", feature = "std", not(secp256k1_fuzz)))] {
+/// # use secp256k1::{rand, ecdsa, Message};
+/// #
+/// # let (secret_key, _) = secp256k1::generate_keypair(&mut rand::rng());
+/// #
+/// let message = Message::from_digest([0xab; 32]);
+/// let (sig, attempts) = ecdsa::sign_grind(message, &secret_key, ecdsa::GrindTarget::low_r(64));
+/// assert!(attempts <= 64);
+/// if sig.is_low_r() {
+///     assert_eq!(sig, ecdsa::sign_low_r(message, &secret_key));
+/// }
+/// # }
+/// ```
+pub fn sign_grind(
+    msg: impl Into<Message>,
+    sk: &SecretKey,
+    target: GrindTarget,
+) -> (Signature, u32) {
+    let msg = msg.into();
+    let mut best = sign_with_grind_counter(&msg, sk, 0);
+    let mut attempts = 1;
+    while !target.is_met_by(&best) && attempts < target.max_attempts {
+        let sig = sign_with_grind_counter(&msg, sk, attempts);
+        attempts += 1;
+        if target.is_met_by(&sig) || sig.der_len() < best.der_len() {
+            best = sig;
         }
     }
+    (best, attempts)
 }
 
 /// Constructs a signature for `msg` using the secret key `sk`, RFC6979 nonce
@@ -384,6 +654,110 @@ pub fn verify(sig: &Signature, msg: impl Into<Message>, pk: &PublicKey) -> Resul
     }
 }
 
+/// The number of signatures each thread verifies per borrow of the global context.
+#[cfg(feature = "rayon")]
+const VERIFY_CHUNK_SIZE: usize = 64;
+
+/// Verifies a batch of `(signature, message, public key)` triples and returns the result of each
+/// one, in the order of `items`.
+///
+/// This is equivalent to calling [`verify`] on every item but borrows the global context only
+/// once. With the `rayon` feature the items are verified on rayon's thread pool.
+///
+/// ```rust
+/// # #[cfg(all(feature = "rand", feature = "std"))] {
+/// # use secp256k1::{rand, ecdsa, Message, Error};
+/// #
+/// # let (secret_key, public_key) = secp256k1::generate_keypair(&mut rand::rng());
+/// #
+/// let good = Message::from_digest([0xab; 32]);
+/// let bad = Message::from_digest([0xcd; 32]);
+/// let sig = ecdsa::sign(good, &secret_key);
+///
+/// let results = ecdsa::verify_many(&[(sig, good, public_key), (sig, bad, public_key)]);
+/// assert_eq!(results, vec![Ok(()), Err(Error::IncorrectSignature)]);
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn verify_many(items: &[(Signature, Message, PublicKey)]) -> Vec<Result<(), Error>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        items.par_chunks(VERIFY_CHUNK_SIZE).flat_map_iter(verify_chunk).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    verify_chunk(items)
+}
+
+/// Verifies a batch of `(signature, message, public key)` triples, stopping at the first one
+/// that is invalid.
+///
+/// Returns `Ok(())` if all signatures are valid and otherwise the index of the first invalid
+/// item. With the `rayon` feature the items are verified on rayon's thread pool, and the
+/// remaining work is abandoned as soon as an invalid item is found.
+pub fn verify_all(items: &[(Signature, Message, PublicKey)]) -> Result<(), usize> {
+    #[cfg(feature = "rayon")]
+    let first_failure = {
+        use rayon::prelude::*;
+        items.par_chunks(VERIFY_CHUNK_SIZE).enumerate().find_map_first(|(i, chunk)| {
+            first_failure(chunk).map(|pos| i * VERIFY_CHUNK_SIZE + pos)
+        })
+    };
+    #[cfg(not(feature = "rayon"))]
+    let first_failure = first_failure(items);
+
+    match first_failure {
+        Some(pos) => Err(pos),
+        None => Ok(()),
+    }
+}
+
+/// Verifies `items` with a single borrow of the global context.
+#[cfg(feature = "alloc")]
+fn verify_chunk(items: &[(Signature, Message, PublicKey)]) -> Vec<Result<(), Error>> {
+    crate::with_raw_global_context(
+        |ctx| {
+            items
+                .iter()
+                .map(|(sig, msg, pk)| {
+                    let res = unsafe {
+                        ffi::secp256k1_ecdsa_verify(
+                            ctx.as_ptr(),
+                            sig.as_c_ptr(),
+                            msg.as_c_ptr(),
+                            pk.as_c_ptr(),
+                        )
+                    };
+                    if res == 0 {
+                        Err(Error::IncorrectSignature)
+                    } else {
+                        Ok(())
+                    }
+                })
+                .collect()
+        },
+        None,
+    )
+}
+
+/// Returns the index of the first invalid item in `items`, using a single borrow of the global
+/// context.
+fn first_failure(items: &[(Signature, Message, PublicKey)]) -> Option<usize> {
+    crate::with_raw_global_context(
+        |ctx| {
+            items.iter().position(|(sig, msg, pk)| unsafe {
+                ffi::secp256k1_ecdsa_verify(
+                    ctx.as_ptr(),
+                    sig.as_c_ptr(),
+                    msg.as_c_ptr(),
+                    pk.as_c_ptr(),
+                ) == 0
+            })
+        },
+        None,
+    )
+}
+
 pub(crate) fn compact_sig_has_zero_first_bit(sig: &ffi::Signature) -> bool {
     let mut compact = [0u8; 64];
     unsafe {
@@ -415,7 +789,7 @@ pub(crate) fn der_length_check(sig: &ffi::Signature, max_len: usize) -> bool {
 #[cfg(test)]
 mod tests {
     use crate::ecdsa::Signature;
-    use crate::Scalar;
+    use crate::{Message, PublicKey, Scalar, SecretKey};
 
     #[test]
     fn test_from_compact_min_r_and_min_s() {
@@ -465,4 +839,209 @@ mod tests {
 
         assert!(Signature::from_compact(&bytes).is_err())
     }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))] // fuzz-sigs never call the nonce function
+    fn sign_with_nonce_fn() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = Message::from_digest([0xab; 32]);
+        let nonce = crate::test_random_32_bytes();
+
+        // A panic in the callback aborts without `std`, so record its arguments and check them
+        // afterwards.
+        let mut calls = [None; 2];
+        let mut n_calls = 0;
+        let sig = super::sign_with_nonce_fn(msg, &sk, |msg32, key32, algo16, attempt| {
+            if let Some(call) = calls.get_mut(n_calls) {
+                *call = Some((*msg32, *key32, algo16.copied(), attempt));
+            }
+            n_calls += 1;
+            // The zero nonce is invalid, so the first attempt is retried.
+            if attempt == 0 {
+                Some([0; 32])
+            } else {
+                Some(nonce)
+            }
+        })
+        .unwrap();
+        assert_eq!(n_calls, 2);
+        for (i, call) in calls.iter().enumerate() {
+            let (msg32, key32, algo16, attempt) = call.unwrap();
+            assert_eq!(msg32, *msg.as_ref());
+            assert_eq!(key32, sk.to_secret_bytes());
+            assert_eq!(algo16, None);
+            assert_eq!(attempt, i as u32);
+        }
+        assert_eq!(super::verify(&sig, msg, &pk), Ok(()));
+
+        // The r value is the x coordinate of the nonce point.
+        let nonce_point = PublicKey::from_secret_key(&SecretKey::from_secret_bytes(nonce).unwrap());
+        assert_eq!(sig.serialize_compact()[..32], nonce_point.serialize()[1..]);
+
+        assert_eq!(super::sign_with_nonce_fn(msg, &sk, |_, _, _, _| None), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", not(secp256k1_fuzz)))]
+    fn sign_with_nonce_fn_panic() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = Message::from_digest([0xab; 32]);
+
+        let result = std::panic::catch_unwind(|| {
+            super::sign_with_nonce_fn(msg, &sk, |_, _, _, _| panic!("nonce function failed"))
+        });
+        let payload = result.unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"nonce function failed"));
+
+        // The global context is released and still usable.
+        let sig = super::sign(msg, &sk);
+        assert_eq!(super::verify(&sig, msg, &pk), Ok(()));
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(secp256k1_fuzz)))]
+    fn verify_many() {
+        use crate::alloc::vec::Vec;
+        use crate::Error;
+
+        // Enough items to span several chunks when verifying in parallel.
+        let mut items = Vec::new();
+        for i in 0..150u8 {
+            let (sk, pk) = crate::test_random_keypair();
+            let msg = Message::from_digest([i; 32]);
+            items.push((super::sign(msg, &sk), msg, pk));
+        }
+        assert_eq!(super::verify_many(&items), vec![Ok(()); 150]);
+        assert_eq!(super::verify_all(&items), Ok(()));
+        assert_eq!(super::verify_many(&[]), vec![]);
+        assert_eq!(super::verify_all(&[]), Ok(()));
+
+        let bad = [7, 70, 149];
+        for &i in &bad {
+            items[i].1 = Message::from_digest([0xff; 32]);
+        }
+        let results = super::verify_many(&items);
+        for (i, res) in results.iter().enumerate() {
+            if bad.contains(&i) {
+                assert_eq!(*res, Err(Error::IncorrectSignature));
+            } else {
+                assert_eq!(*res, Ok(()));
+            }
+        }
+        assert_eq!(super::verify_all(&items), Err(7));
+        assert_eq!(super::verify_all(&items[8..]), Err(62));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn low_s_and_malleate() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = Message::from_digest(crate::test_random_32_bytes());
+        let sig = super::sign(msg, &sk);
+        assert!(sig.is_low_s());
+
+        let twin = sig.malleate();
+        assert!(!twin.is_low_s());
+        assert_ne!(twin, sig);
+        assert_eq!(twin.serialize_compact()[..32], sig.serialize_compact()[..32]);
+        assert_eq!(twin.malleate(), sig);
+        // The twin only verifies once normalized.
+        assert!(super::verify(&twin, msg, &pk).is_err());
+        let mut normalized = twin;
+        normalized.normalize_s();
+        assert_eq!(normalized, sig);
+
+        // Zero is its own negation.
+        let zero = Signature::from_compact(&[0; 64]).unwrap();
+        assert_eq!(zero.malleate(), zero);
+        assert!(zero.is_low_s());
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn low_r() {
+        let (sk, _) = crate::test_random_keypair();
+        let msg = Message::from_digest(crate::test_random_32_bytes());
+        let sig = super::sign_low_r(msg, &sk);
+        assert!(sig.is_low_r());
+        assert!(sig.der_len() <= 70);
+
+        let mut compact = sig.serialize_compact();
+        compact[0] = 0x80;
+        assert!(!Signature::from_compact(&compact).unwrap().is_low_r());
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn der_len() {
+        let (sk, _) = crate::test_random_keypair();
+        for i in 0..64u8 {
+            let sig = super::sign(Message::from_digest([i; 32]), &sk);
+            assert_eq!(sig.der_len(), sig.serialize_der().len());
+            assert_eq!(sig.malleate().der_len(), sig.malleate().serialize_der().len());
+        }
+
+        let mut compact = [0u8; 64];
+        assert_eq!(Signature::from_compact(&compact).unwrap().der_len(), 8);
+        compact[31] = 0x80;
+        compact[63] = 0x7f;
+        assert_eq!(Signature::from_compact(&compact).unwrap().der_len(), 9);
+        compact[0] = 0x80;
+        compact[32] = 0x80;
+        assert_eq!(Signature::from_compact(&compact).unwrap().der_len(), 72);
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn sign_grind() {
+        use super::GrindTarget;
+
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = Message::from_digest(crate::test_random_32_bytes());
+
+        let (sig, attempts) = super::sign_grind(msg, &sk, GrindTarget::low_r(u32::MAX));
+        assert_eq!(sig, super::sign_low_r(msg, &sk));
+        assert_eq!(super::verify(&sig, msg, &pk), Ok(()));
+        assert!(attempts >= 1);
+
+        // A length-only target also accepts a high `r` offset by a short `s`.
+        let target = GrindTarget { max_der_len: 70, low_r: false, max_attempts: u32::MAX };
+        let (sig, _) = super::sign_grind(msg, &sk, target);
+        assert!(sig.der_len() <= 70);
+
+        // A single attempt is a plain signature.
+        let target = GrindTarget { max_der_len: 0, low_r: false, max_attempts: 0 };
+        assert_eq!(super::sign_grind(msg, &sk, target), (super::sign(msg, &sk), 1));
+
+        // An unreachable target uses the whole budget and keeps the earliest shortest signature.
+        let target = GrindTarget { max_der_len: 0, low_r: false, max_attempts: 20 };
+        let (sig, attempts) = super::sign_grind(msg, &sk, target);
+        assert_eq!(attempts, 20);
+        let expected = (0..20)
+            .map(|i| super::sign_with_grind_counter(&msg, &sk, i))
+            .min_by_key(Signature::der_len)
+            .unwrap();
+        assert_eq!(sig, expected);
+        assert_eq!(super::verify(&sig, msg, &pk), Ok(()));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn sign_grind_vector() {
+        use core::str::FromStr;
+
+        // The same vector as `sign_grind_r` with two bytes of grinding.
+        let msg = Message::from_digest(hex_lit::hex!(
+            "ef2d5b9a7c61865a95941d0f04285420560df7e9d76890ac1b8867b12ce43167"
+        ));
+        let sk =
+            SecretKey::from_str("848355d75fe1c354cf05539bb29b2015f1863065bcb6766b44d399ab95c3fa0b")
+                .unwrap();
+        let expected = Signature::from_str("304302202ffc447100d518c8ba643d11f3e6a83a8640488e7d2537b1954b942408be6ea3021f26e1248dd1e52160c3a38af9769d91a1a806cab5f9d508c103464d3c02d6e1").unwrap();
+
+        let target = super::GrindTarget { max_der_len: 69, low_r: false, max_attempts: 1000 };
+        let (sig, attempts) = super::sign_grind(msg, &sk, target);
+        assert_eq!(sig, expected);
+        assert_eq!(super::sign_with_grind_counter(&msg, &sk, attempts - 1), expected);
+    }
 }
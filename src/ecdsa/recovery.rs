@@ -4,10 +4,12 @@
 //! signature.
 //!
 
-use core::ptr;
+use core::ptr::{self, NonNull};
 
 use self::super_ffi::CPtr;
 use super::ffi as super_ffi;
+#[cfg(feature = "alloc")]
+use crate::alloc::vec::Vec;
 use crate::ecdsa::Signature;
 use crate::ffi::recovery as ffi;
 use crate::{key, Error, Message};
@@ -109,6 +111,86 @@ impl RecoverableSignature {
         (recid.try_into().expect("ffi returned invalid RecoveryId!"), ret)
     }
 
+    /// Serializes the recoverable signature as the 64-byte compact signature followed by the
+    /// recovery ID as a single byte.
+    #[inline]
+    pub fn to_byte_array(&self) -> [u8; 65] {
+        let (recid, compact) = self.serialize_compact();
+        let mut ret = [0u8; 65];
+        ret[..64].copy_from_slice(&compact);
+        ret[64] = recid.to_u8();
+        ret
+    }
+
+    /// Parses a recoverable signature in the 65-byte layout produced by
+    /// [`RecoverableSignature::to_byte_array`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRecoveryId`] if the last byte is not in the range `0..=3`.
+    #[inline]
+    pub fn from_byte_array(data: &[u8; 65]) -> Result<RecoverableSignature, Error> {
+        let recid = RecoveryId::try_from(i32::from(data[64]))?;
+        RecoverableSignature::from_compact(&data[..64], recid)
+    }
+
+    /// Serializes the signature in the 64-byte [EIP-2098] form, `r || s` with the recovery ID
+    /// stored in the top bit of `s`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRecoveryId`] for recovery IDs 2 and 3, which this form cannot
+    /// represent, and [`Error::InvalidSignature`] if `s` is high, because its top bit may then be
+    /// set. Call [`RecoverableSignature::normalize_s`] first to encode a high-S signature.
+    ///
+    /// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+    pub fn to_compact_2098(&self) -> Result<[u8; 64], Error> {
+        let (recid, mut compact) = self.serialize_compact();
+        if !self.to_standard().is_low_s() {
+            return Err(Error::InvalidSignature);
+        }
+        match recid {
+            RecoveryId::Zero => {}
+            RecoveryId::One => compact[32] |= 0x80,
+            RecoveryId::Two | RecoveryId::Three => return Err(Error::InvalidRecoveryId),
+        }
+        Ok(compact)
+    }
+
+    /// Parses a signature in the 64-byte [EIP-2098] form produced by
+    /// [`RecoverableSignature::to_compact_2098`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSignature`] if `r` or `s` are out of range or if `s` is high.
+    ///
+    /// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+    pub fn from_compact_2098(data: &[u8; 64]) -> Result<RecoverableSignature, Error> {
+        let mut compact = *data;
+        let recid = if compact[32] & 0x80 == 0 { RecoveryId::Zero } else { RecoveryId::One };
+        compact[32] &= 0x7f;
+        let sig = RecoverableSignature::from_compact(&compact, recid)?;
+        if !sig.to_standard().is_low_s() {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(sig)
+    }
+
+    /// Normalizes the signature to a "low S" form, see [`Signature::normalize_s`].
+    ///
+    /// Negating `s` also negates the nonce point, so the recovery ID is updated to the other
+    /// parity to keep recovering the same public key.
+    pub fn normalize_s(&mut self) {
+        let (recid, compact) = self.serialize_compact();
+        let sig = Signature::from_compact(&compact).expect("valid compact signature");
+        if !sig.is_low_s() {
+            let flipped = RecoveryId::from_u8_masked(recid.to_u8() ^ 1);
+            *self =
+                RecoverableSignature::from_compact(&sig.malleate().serialize_compact(), flipped)
+                    .expect("valid compact signature");
+        }
+    }
+
     /// Converts a recoverable signature to a non-recoverable one (this is needed
     /// for verification).
     #[inline]
@@ -134,6 +216,93 @@ impl RecoverableSignature {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for RecoverableSignature {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            let mut buf = [0u8; 130];
+            s.serialize_str(
+                crate::to_hex(&self.to_byte_array(), &mut buf)
+                    .expect("fixed-size hex serialization"),
+            )
+        } else {
+            s.serialize_bytes(&self.to_byte_array())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RecoverableSignature {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        fn parse(slice: &[u8]) -> Result<RecoverableSignature, Error> {
+            let bytes: &[u8; 65] = slice.try_into().map_err(|_| Error::InvalidSignature)?;
+            RecoverableSignature::from_byte_array(bytes)
+        }
+
+        if d.is_human_readable() {
+            d.deserialize_str(crate::serde_util::HexVisitor::<_, 65>::new(
+                "a hex string representing a 65 byte recoverable signature",
+                parse,
+            ))
+        } else {
+            d.deserialize_bytes(crate::serde_util::BytesVisitor::new(
+                "raw 65 bytes recoverable signature",
+                parse,
+            ))
+        }
+    }
+}
+
+/// Serde support for [`RecoverableSignature`] in the 64-byte [EIP-2098] form.
+///
+/// Use it with `#[serde(with = "secp256k1::ecdsa::serde_compact_2098")]`. Human-readable formats
+/// use a hex string, binary formats use the raw bytes.
+///
+/// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+#[cfg(feature = "serde")]
+pub mod serde_compact_2098 {
+    use super::RecoverableSignature;
+    use crate::Error;
+
+    /// Serializes `sig` with [`RecoverableSignature::to_compact_2098`].
+    pub fn serialize<S: serde::Serializer>(
+        sig: &RecoverableSignature,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        let compact = sig.to_compact_2098().map_err(serde::ser::Error::custom)?;
+        if s.is_human_readable() {
+            let mut buf = [0u8; 128];
+            s.serialize_str(
+                crate::to_hex(&compact, &mut buf).expect("fixed-size hex serialization"),
+            )
+        } else {
+            s.serialize_bytes(&compact)
+        }
+    }
+
+    /// Deserializes a signature with [`RecoverableSignature::from_compact_2098`].
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        d: D,
+    ) -> Result<RecoverableSignature, D::Error> {
+        fn parse(slice: &[u8]) -> Result<RecoverableSignature, Error> {
+            let bytes: &[u8; 64] = slice.try_into().map_err(|_| Error::InvalidSignature)?;
+            RecoverableSignature::from_compact_2098(bytes)
+        }
+
+        if d.is_human_readable() {
+            d.deserialize_str(crate::serde_util::HexVisitor::<_, 64>::new(
+                "a hex string representing a 64 byte EIP-2098 signature",
+                parse,
+            ))
+        } else {
+            d.deserialize_bytes(crate::serde_util::BytesVisitor::new(
+                "raw 64 bytes EIP-2098 signature",
+                parse,
+            ))
+        }
+    }
+}
+
 impl CPtr for RecoverableSignature {
     type Target = ffi::RecoverableSignature;
     fn as_c_ptr(&self) -> *const Self::Target { &self.0 }
@@ -227,11 +396,84 @@ impl RecoverableSignature {
             None,
         )
     }
+
+    /// Converts a standard signature of `pk` on `msg` into a recoverable one, by finding the
+    /// [`RecoveryId`] under which `pk` is recovered.
+    ///
+    /// This is useful for signatures produced by devices that do not report a recovery ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::IncorrectSignature`] if `sig` is not a valid signature of `pk` on `msg`.
+    pub fn from_standard(
+        sig: &Signature,
+        msg: impl Into<Message>,
+        pk: &key::PublicKey,
+    ) -> Result<RecoverableSignature, Error> {
+        let msg = msg.into();
+        crate::with_raw_global_context(
+            |ctx| unsafe { Self::from_standard_with_ctx(ctx, sig, &msg, pk) },
+            None,
+        )
+    }
+
+    /// Converts many standard signatures into recoverable ones, see
+    /// [`RecoverableSignature::from_standard`].
+    ///
+    /// Like [`verify_many`](super::verify_many), this borrows the global context once for the
+    /// whole slice instead of once per item. The results are in the same order as `items`.
+    #[cfg(feature = "alloc")]
+    pub fn from_standard_batch(
+        items: &[(Signature, Message, key::PublicKey)],
+    ) -> Vec<Result<RecoverableSignature, Error>> {
+        crate::with_raw_global_context(
+            |ctx| {
+                items
+                    .iter()
+                    .map(|(sig, msg, pk)| unsafe {
+                        Self::from_standard_with_ctx(ctx, sig, msg, pk)
+                    })
+                    .collect()
+            },
+            None,
+        )
+    }
+
+    /// Tries all recovery IDs of `sig` until one of them recovers `pk`.
+    ///
+    /// # Safety
+    ///
+    /// `ctx` must be a valid context pointer.
+    unsafe fn from_standard_with_ctx(
+        ctx: NonNull<super_ffi::Context>,
+        sig: &Signature,
+        msg: &Message,
+        pk: &key::PublicKey,
+    ) -> Result<RecoverableSignature, Error> {
+        let compact = sig.serialize_compact();
+        for recid in [RecoveryId::Zero, RecoveryId::One, RecoveryId::Two, RecoveryId::Three] {
+            let rec_sig = RecoverableSignature::from_compact(&compact, recid)?;
+            let mut recovered = super_ffi::PublicKey::new();
+            // Recovery IDs 2 and 3 fail early unless r is tiny, so trying them is cheap.
+            if ffi::secp256k1_ecdsa_recover(
+                ctx.as_ptr(),
+                &mut recovered,
+                rec_sig.as_c_ptr(),
+                msg.as_c_ptr(),
+            ) == 1
+                && key::PublicKey::from(recovered) == *pk
+            {
+                return Ok(rec_sig);
+            }
+        }
+        Err(Error::IncorrectSignature)
+    }
 }
 
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
+    use hex_lit::hex;
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test as test;
 
@@ -395,6 +637,172 @@ mod tests {
         let id1 = RecoveryId::One;
         assert_eq!(Into::<u8>::into(id1), 1u8);
     }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn from_standard() {
+        let msg = Message::from_digest(crate::test_random_32_bytes());
+        let (sk, pk) = crate::test_random_keypair();
+        let (_, other_pk) = crate::test_random_keypair();
+
+        let sig = RecoverableSignature::sign_ecdsa_recoverable(msg, &sk);
+        let standard = sig.to_standard();
+        assert_eq!(RecoverableSignature::from_standard(&standard, msg, &pk), Ok(sig));
+        assert_eq!(
+            RecoverableSignature::from_standard(&standard, msg, &other_pk),
+            Err(Error::IncorrectSignature)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(secp256k1_fuzz)))]
+    fn from_standard_batch() {
+        let mut items = crate::alloc::vec::Vec::new();
+        let mut expected = crate::alloc::vec::Vec::new();
+        for _ in 0..8 {
+            let msg = Message::from_digest(crate::test_random_32_bytes());
+            let (sk, pk) = crate::test_random_keypair();
+            let sig = RecoverableSignature::sign_ecdsa_recoverable(msg, &sk);
+            items.push((sig.to_standard(), msg, pk));
+            expected.push(Ok(sig));
+        }
+        // A signature checked against the wrong key.
+        items[3].2 = items[4].2;
+        expected[3] = Err(Error::IncorrectSignature);
+
+        assert_eq!(RecoverableSignature::from_standard_batch(&items), expected);
+    }
+
+    #[test]
+    fn byte_array_roundtrip() {
+        let mut bytes = [0x11u8; 65];
+        bytes[64] = 2;
+        let sig = RecoverableSignature::from_byte_array(&bytes).unwrap();
+        assert_eq!(sig.serialize_compact(), (RecoveryId::Two, [0x11; 64]));
+        assert_eq!(sig.to_byte_array(), bytes);
+
+        bytes[64] = 4;
+        assert_eq!(RecoverableSignature::from_byte_array(&bytes), Err(Error::InvalidRecoveryId));
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    fn serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+        #[rustfmt::skip]
+        static BYTES: [u8; 65] = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+            0x01,
+        ];
+        static STR: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+                            202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f01";
+
+        let sig = RecoverableSignature::from_byte_array(&BYTES).unwrap();
+
+        assert_tokens(&sig.compact(), &[Token::BorrowedBytes(&BYTES[..])]);
+        assert_tokens(&sig.compact(), &[Token::Bytes(&BYTES)]);
+        assert_tokens(&sig.compact(), &[Token::ByteBuf(&BYTES)]);
+
+        assert_tokens(&sig.readable(), &[Token::BorrowedStr(STR)]);
+        assert_tokens(&sig.readable(), &[Token::Str(STR)]);
+        assert_tokens(&sig.readable(), &[Token::String(STR)]);
+
+        assert_de_tokens_error::<serde_test::Compact<RecoverableSignature>>(
+            &[Token::Bytes(&BYTES[..64])],
+            "malformed signature",
+        );
+        assert_de_tokens_error::<serde_test::Readable<RecoverableSignature>>(
+            &[Token::Str(&STR[..128])],
+            "malformed signature",
+        );
+        assert_de_tokens_error::<serde_test::Readable<RecoverableSignature>>(
+            &[Token::Str(
+                "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+                          202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f04",
+            )],
+            "bad recovery id",
+        );
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn normalize_s() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = Message::from_digest(crate::test_random_32_bytes());
+        let sig = RecoverableSignature::sign_ecdsa_recoverable(msg, &sk);
+        let (recid, compact) = sig.serialize_compact();
+
+        let twin = sig.to_standard().malleate();
+        let flipped = RecoveryId::from_u8_masked(recid.to_u8() ^ 1);
+        let mut high =
+            RecoverableSignature::from_compact(&twin.serialize_compact(), flipped).unwrap();
+        assert_eq!(high.recover_ecdsa(msg), Ok(pk));
+
+        high.normalize_s();
+        assert_eq!(high, sig);
+        assert_eq!(high.serialize_compact(), (recid, compact));
+        high.normalize_s();
+        assert_eq!(high, sig);
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn compact_2098() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = Message::from_digest(crate::test_random_32_bytes());
+        let sig = RecoverableSignature::sign_ecdsa_recoverable(msg, &sk);
+        let (recid, compact) = sig.serialize_compact();
+
+        let packed = sig.to_compact_2098().unwrap();
+        assert_eq!(packed[..32], compact[..32]);
+        assert_eq!(packed[32] >> 7, recid.to_u8());
+        let parsed = RecoverableSignature::from_compact_2098(&packed).unwrap();
+        assert_eq!(parsed, sig);
+        assert_eq!(parsed.recover_ecdsa(msg), Ok(pk));
+
+        let high = RecoverableSignature::from_compact(
+            &sig.to_standard().malleate().serialize_compact(),
+            RecoveryId::from_u8_masked(recid.to_u8() ^ 1),
+        )
+        .unwrap();
+        assert_eq!(high.to_compact_2098(), Err(Error::InvalidSignature));
+        let two = RecoverableSignature::from_compact(&compact, RecoveryId::Two).unwrap();
+        assert_eq!(two.to_compact_2098(), Err(Error::InvalidRecoveryId));
+    }
+
+    #[test]
+    fn compact_2098_vectors() {
+        // The examples from EIP-2098.
+        let r = hex!("68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90");
+        let s = hex!("7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064");
+        let packed = hex!("68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b907e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064");
+        let sig = RecoverableSignature::from_compact_2098(&packed).unwrap();
+        let (recid, compact) = sig.serialize_compact();
+        assert_eq!((recid, &compact[..32], &compact[32..]), (RecoveryId::Zero, &r[..], &s[..]));
+        assert_eq!(sig.to_compact_2098(), Ok(packed));
+
+        let r = hex!("9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76");
+        let s = hex!("139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793");
+        let packed = hex!("9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793");
+        let sig = RecoverableSignature::from_compact_2098(&packed).unwrap();
+        let (recid, compact) = sig.serialize_compact();
+        assert_eq!((recid, &compact[..32], &compact[32..]), (RecoveryId::One, &r[..], &s[..]));
+        assert_eq!(sig.to_compact_2098(), Ok(packed));
+
+        // A high s without the parity bit.
+        let mut high = [0xff; 64];
+        high[..32].copy_from_slice(&r);
+        high[32] = 0x7f;
+        assert_eq!(RecoverableSignature::from_compact_2098(&high), Err(Error::InvalidSignature));
+    }
 }
 
 #[cfg(bench)]
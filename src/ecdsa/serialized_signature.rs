@@ -135,6 +135,39 @@ impl<'a> TryFrom<&'a SerializedSignature> for Signature {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for SerializedSignature {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.collect_str(self)
+        } else {
+            s.serialize_bytes(self)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SerializedSignature {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        // Going through `Signature` makes sure we only ever hold valid DER.
+        fn parse(slice: &[u8]) -> Result<SerializedSignature, Error> {
+            Signature::from_der(slice).map(|sig| sig.serialize_der())
+        }
+
+        if d.is_human_readable() {
+            d.deserialize_str(crate::serde_util::HexVisitor::<_, MAX_LEN>::new(
+                "a hex string representing a DER encoded Signature",
+                parse,
+            ))
+        } else {
+            d.deserialize_bytes(crate::serde_util::BytesVisitor::new(
+                "raw byte stream, that represents a DER encoded Signature",
+                parse,
+            ))
+        }
+    }
+}
+
 impl SerializedSignature {
     /// Creates `SerializedSignature` from data and length.
     ///
@@ -298,4 +331,41 @@ mod tests {
             assert_eq!(iter1.as_slice(), iter2.as_slice());
         }
     }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    fn serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+        #[rustfmt::skip]
+        static DER: [u8; 71] = [
+            0x30, 0x45, 0x02, 0x21, 0x00, 0xdc, 0x4d, 0xc2,
+            0x64, 0xa9, 0xfe, 0xf1, 0x7a, 0x3f, 0x25, 0x34,
+            0x49, 0xcf, 0x8c, 0x39, 0x7a, 0xb6, 0xf1, 0x6f,
+            0xb3, 0xd6, 0x3d, 0x86, 0x94, 0x0b, 0x55, 0x86,
+            0x82, 0x3d, 0xfd, 0x02, 0xae, 0x02, 0x20, 0x3b,
+            0x46, 0x1b, 0xb4, 0x33, 0x6b, 0x5e, 0xcb, 0xae,
+            0xfd, 0x66, 0x27, 0xaa, 0x92, 0x2e, 0xfc, 0x04,
+            0x8f, 0xec, 0x0c, 0x88, 0x1c, 0x10, 0xc4, 0xc9,
+            0x42, 0x8f, 0xca, 0x69, 0xc1, 0x32, 0xa2,
+        ];
+        static STR: &str =
+            "3045022100dc4dc264a9fef17a3f253449cf8c397ab6f16fb3d63d86940b5586823dfd02ae\
+                            02203b461bb4336b5ecbaefd6627aa922efc048fec0c881c10c4c9428fca69c132a2";
+
+        let sig = Signature::from_der(&DER).unwrap().serialize_der();
+        assert_eq!(&sig[..], &DER[..]);
+
+        assert_tokens(&sig.compact(), &[Token::BorrowedBytes(&DER[..])]);
+        assert_tokens(&sig.compact(), &[Token::Bytes(&DER)]);
+        assert_tokens(&sig.compact(), &[Token::ByteBuf(&DER)]);
+        assert_tokens(&sig.readable(), &[Token::BorrowedStr(STR)]);
+        assert_tokens(&sig.readable(), &[Token::Str(STR)]);
+        assert_tokens(&sig.readable(), &[Token::String(STR)]);
+
+        assert_de_tokens_error::<serde_test::Compact<SerializedSignature>>(
+            &[Token::Bytes(&DER[..70])],
+            "malformed signature",
+        );
+    }
 }
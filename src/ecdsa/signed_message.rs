@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Signing and verification of messages in the legacy "Bitcoin Signed Message" format.
+//!
+//! The message is prefixed with [`BITCOIN_SIGNED_MSG_PREFIX`] and its length, then hashed with
+//! double SHA-256. The 65-byte signature consists of a header byte followed by the compact
+//! signature and is usually transported as base64. The header byte encodes the [`RecoveryId`]
+//! and, following [BIP-137], the type of address the signing key controls:
+//!
+//! | Header | Address type                   |
+//! |--------|--------------------------------|
+//! | 27-30  | P2PKH, uncompressed public key |
+//! | 31-34  | P2PKH, compressed public key   |
+//! | 35-38  | P2SH-P2WPKH                    |
+//! | 39-42  | P2WPKH                         |
+//!
+//! Electrum uses the 31-34 range for all compressed keys, including segwit addresses. Such
+//! signatures parse as [`AddressType::P2pkh`] and verify against the same public key.
+//!
+//! [BIP-137]: https://github.com/bitcoin/bips/blob/master/bip-0137.mediawiki
+
+use core::{fmt, str};
+
+use crate::ecdsa::{RecoverableSignature, RecoveryId};
+use crate::hashes::sha256;
+use crate::{base64, Error, Message, PublicKey, SecretKey};
+
+/// The prefix of every signed message, including its own length byte.
+pub const BITCOIN_SIGNED_MSG_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+/// The size of a serialized [`MessageSignature`].
+pub const MESSAGE_SIGNATURE_SIZE: usize = 65;
+
+/// The length of the base64 encoding of a [`MessageSignature`].
+const BASE64_SIZE: usize = base64::encoded_len(MESSAGE_SIGNATURE_SIZE);
+
+/// Computes the hash that is signed for `msg`.
+///
+/// This is the double SHA-256 of [`BITCOIN_SIGNED_MSG_PREFIX`], the length of `msg` encoded as
+/// a Bitcoin `CompactSize` and `msg` itself.
+pub fn signed_msg_hash(msg: &[u8]) -> Message {
+    let len = msg.len() as u64;
+    let mut varint = [0u8; 9];
+    let varint = match len {
+        0..=0xfc => {
+            varint[0] = len as u8;
+            &varint[..1]
+        }
+        0xfd..=0xffff => {
+            varint[0] = 0xfd;
+            varint[1..3].copy_from_slice(&(len as u16).to_le_bytes());
+            &varint[..3]
+        }
+        0x10000..=0xffff_ffff => {
+            varint[0] = 0xfe;
+            varint[1..5].copy_from_slice(&(len as u32).to_le_bytes());
+            &varint[..5]
+        }
+        _ => {
+            varint[0] = 0xff;
+            varint[1..9].copy_from_slice(&len.to_le_bytes());
+            &varint[..9]
+        }
+    };
+
+    let mut engine = sha256::HashEngine::default();
+    engine.input(BITCOIN_SIGNED_MSG_PREFIX);
+    engine.input(varint);
+    engine.input(msg);
+    Message::from_digest(sha256::hash(&engine.finalize()))
+}
+
+/// The type of address a [`MessageSignature`] is made for, as encoded in its header byte.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AddressType {
+    /// Pay to public key hash, with an uncompressed public key.
+    P2pkhUncompressed,
+    /// Pay to public key hash, with a compressed public key.
+    P2pkh,
+    /// Pay to witness public key hash, nested in pay to script hash.
+    P2shP2wpkh,
+    /// Pay to witness public key hash.
+    P2wpkh,
+}
+
+impl AddressType {
+    /// Returns whether the address commits to the compressed serialization of the public key.
+    pub fn is_compressed(self) -> bool { self != AddressType::P2pkhUncompressed }
+
+    /// Returns the smallest header byte of this address type's range.
+    fn header_base(self) -> u8 {
+        match self {
+            AddressType::P2pkhUncompressed => 27,
+            AddressType::P2pkh => 31,
+            AddressType::P2shP2wpkh => 35,
+            AddressType::P2wpkh => 39,
+        }
+    }
+}
+
+/// Errors returned when parsing or verifying a [`MessageSignature`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum MessageSignatureError {
+    /// The signature is not valid base64.
+    InvalidBase64,
+    /// The signature does not have the expected length of 65 bytes.
+    InvalidLength(usize),
+    /// The header byte is outside of the range 27-42.
+    InvalidHeader(u8),
+    /// The signature is malformed or no public key can be recovered from it.
+    InvalidSignature,
+    /// The recovered public key does not match the expected one.
+    IncorrectSignature,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MessageSignatureError {}
+
+impl fmt::Display for MessageSignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            MessageSignatureError::InvalidBase64 => f.write_str("invalid base64"),
+            MessageSignatureError::InvalidLength(len) =>
+                write!(f, "message signature must be 65 bytes long, got {}", len),
+            MessageSignatureError::InvalidHeader(header) =>
+                write!(f, "invalid message signature header byte {}", header),
+            MessageSignatureError::InvalidSignature => f.write_str("malformed signature"),
+            MessageSignatureError::IncorrectSignature =>
+                f.write_str("signature was made by a different key"),
+        }
+    }
+}
+
+impl From<Error> for MessageSignatureError {
+    fn from(_: Error) -> Self { MessageSignatureError::InvalidSignature }
+}
+
+/// A recoverable signature over a Bitcoin signed message, together with its address type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MessageSignature {
+    signature: RecoverableSignature,
+    address_type: AddressType,
+}
+
+impl MessageSignature {
+    /// Creates a new message signature from its parts.
+    #[inline]
+    pub fn new(signature: RecoverableSignature, address_type: AddressType) -> Self {
+        MessageSignature { signature, address_type }
+    }
+
+    /// Signs `msg` with `sk` for an address of type `address_type`.
+    pub fn sign(msg: &[u8], sk: &SecretKey, address_type: AddressType) -> Self {
+        let signature = RecoverableSignature::sign_ecdsa_recoverable(signed_msg_hash(msg), sk);
+        MessageSignature { signature, address_type }
+    }
+
+    /// Returns the recoverable signature.
+    #[inline]
+    pub fn signature(&self) -> RecoverableSignature { self.signature }
+
+    /// Returns the address type encoded in the header byte.
+    #[inline]
+    pub fn address_type(&self) -> AddressType { self.address_type }
+
+    /// Serializes the signature as the header byte followed by the compact signature.
+    pub fn serialize(&self) -> [u8; MESSAGE_SIGNATURE_SIZE] {
+        let (recid, compact) = self.signature.serialize_compact();
+        let mut ret = [0u8; MESSAGE_SIGNATURE_SIZE];
+        ret[0] = self.address_type.header_base() + recid.to_u8();
+        ret[1..].copy_from_slice(&compact);
+        ret
+    }
+
+    /// Parses a signature from its 65-byte serialization.
+    pub fn from_byte_array(
+        bytes: &[u8; MESSAGE_SIGNATURE_SIZE],
+    ) -> Result<Self, MessageSignatureError> {
+        let header = bytes[0];
+        let address_type = match header {
+            27..=30 => AddressType::P2pkhUncompressed,
+            31..=34 => AddressType::P2pkh,
+            35..=38 => AddressType::P2shP2wpkh,
+            39..=42 => AddressType::P2wpkh,
+            _ => return Err(MessageSignatureError::InvalidHeader(header)),
+        };
+        let recid = RecoveryId::from_u8_masked(header - 27);
+        let signature = RecoverableSignature::from_compact(&bytes[1..], recid)?;
+        Ok(MessageSignature { signature, address_type })
+    }
+
+    /// Parses a signature from a 65-byte slice.
+    pub fn from_slice(data: &[u8]) -> Result<Self, MessageSignatureError> {
+        match data.try_into() {
+            Ok(bytes) => Self::from_byte_array(bytes),
+            Err(_) => Err(MessageSignatureError::InvalidLength(data.len())),
+        }
+    }
+
+    /// Recovers the public key that produced this signature over `msg`.
+    pub fn recover_pubkey(&self, msg: &[u8]) -> Result<PublicKey, MessageSignatureError> {
+        Ok(self.signature.recover_ecdsa(signed_msg_hash(msg))?)
+    }
+
+    /// Checks that this signature over `msg` was made by `pk`.
+    ///
+    /// Since [`PublicKey`] does not carry a serialization format, this does not check whether
+    /// the key's compression matches the header byte. Callers that verify against an address
+    /// must derive it using [`AddressType::is_compressed`].
+    pub fn verify(&self, msg: &[u8], pk: &PublicKey) -> Result<(), MessageSignatureError> {
+        if self.recover_pubkey(msg)? == *pk {
+            Ok(())
+        } else {
+            Err(MessageSignatureError::IncorrectSignature)
+        }
+    }
+}
+
+impl fmt::Display for MessageSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; BASE64_SIZE];
+        f.write_str(base64::encode(&self.serialize(), &mut buf).expect("fixed-size encoding"))
+    }
+}
+
+impl str::FromStr for MessageSignature {
+    type Err = MessageSignatureError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Check the length first, since longer strings do not fit the buffer.
+        let len = base64::decoded_len(s).map_err(|()| MessageSignatureError::InvalidBase64)?;
+        if len != MESSAGE_SIGNATURE_SIZE {
+            return Err(MessageSignatureError::InvalidLength(len));
+        }
+        let mut buf = [0u8; MESSAGE_SIGNATURE_SIZE];
+        base64::decode(s, &mut buf).expect("valid base64 of the right length");
+        Self::from_byte_array(&buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn msg_hash() {
+        assert_eq!(
+            signed_msg_hash(b"test"),
+            Message::from_digest(hex!(
+                "9ce428d58e8e4caf619dc6fc7b2c2c28f0561654d1f80f322c038ad5e67ff8a6"
+            )),
+        );
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn known_signature() {
+        // From Bitcoin Core's `rpc_signmessage.py`.
+        let sk = SecretKey::from_secret_bytes(hex!(
+            "d2b8a0116d641fe7d3036f8464628fb595b480414c13a301b3d4038c811c28b0"
+        ))
+        .unwrap();
+        let pk = PublicKey::from_secret_key(&sk);
+        let msg = b"This is just a test message";
+        let encoded = "INbVnW4e6PeRmsv2Qgu8NuopvrVjkcxob+sX8OcZG0SALhWybUjzMLPdAsXI46YZGb0KQTRii+wWIQzRpG/U+S0=";
+
+        let sig = MessageSignature::sign(msg, &sk, AddressType::P2pkh);
+        assert_eq!(sig.to_string(), encoded);
+
+        let parsed = MessageSignature::from_str(encoded).unwrap();
+        assert_eq!(parsed, sig);
+        assert_eq!(parsed.address_type(), AddressType::P2pkh);
+        assert_eq!(parsed.recover_pubkey(msg), Ok(pk));
+        assert_eq!(parsed.verify(msg, &pk), Ok(()));
+        assert_eq!(
+            parsed.verify(b"a different message", &pk),
+            Err(MessageSignatureError::IncorrectSignature)
+        );
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn header_ranges() {
+        let (sk, pk) = crate::test_random_keypair();
+        let msg = b"header ranges";
+
+        for (address_type, base) in [
+            (AddressType::P2pkhUncompressed, 27),
+            (AddressType::P2pkh, 31),
+            (AddressType::P2shP2wpkh, 35),
+            (AddressType::P2wpkh, 39),
+        ] {
+            let sig = MessageSignature::sign(msg, &sk, address_type);
+            let bytes = sig.serialize();
+            let (recid, _) = sig.signature().serialize_compact();
+            assert_eq!(bytes[0], base + recid.to_u8());
+            assert_eq!(address_type.is_compressed(), base != 27);
+
+            let parsed = MessageSignature::from_byte_array(&bytes).unwrap();
+            assert_eq!(parsed, sig);
+            assert_eq!(parsed.verify(msg, &pk), Ok(()));
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let mut bytes = [1u8; MESSAGE_SIGNATURE_SIZE];
+        for header in [0, 26, 43, 255] {
+            bytes[0] = header;
+            assert_eq!(
+                MessageSignature::from_byte_array(&bytes),
+                Err(MessageSignatureError::InvalidHeader(header))
+            );
+        }
+        assert_eq!(
+            MessageSignature::from_slice(&bytes[..64]),
+            Err(MessageSignatureError::InvalidLength(64))
+        );
+        assert_eq!(MessageSignature::from_str("!"), Err(MessageSignatureError::InvalidBase64));
+        assert_eq!(
+            MessageSignature::from_str("Zm9v"),
+            Err(MessageSignatureError::InvalidLength(3))
+        );
+        // Strings longer than the encoding of a signature.
+        let long = "A".repeat(BASE64_SIZE);
+        assert_eq!(
+            MessageSignature::from_str(&long),
+            Err(MessageSignatureError::InvalidLength(66))
+        );
+        assert_eq!(
+            MessageSignature::from_str(&(long.clone() + "AA==")),
+            Err(MessageSignatureError::InvalidLength(67))
+        );
+        assert_eq!(
+            MessageSignature::from_str(&(long + "!AAA")),
+            Err(MessageSignatureError::InvalidBase64)
+        );
+    }
+}
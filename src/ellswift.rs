@@ -42,6 +42,7 @@ use core::str::FromStr;
 use ffi::CPtr;
 use secp256k1_sys::types::{c_int, c_uchar, c_void};
 
+use crate::hashes::hkdf;
 use crate::{constants, ffi, from_hex, Error, PublicKey, Secp256k1, SecretKey};
 
 unsafe extern "C" fn hash_callback<F>(
@@ -277,6 +278,15 @@ impl ElligatorSwiftSharedSecret {
 
     /// Returns the secret bytes as a reference to an array.
     pub const fn as_secret_bytes(&self) -> &[u8; 32] { &self.0 }
+
+    /// Derives keying material from the shared secret with HKDF-SHA256 (RFC 5869), filling `out`.
+    ///
+    /// # Panics
+    ///
+    /// If `out` is longer than 8160 bytes, the HKDF output limit.
+    pub fn derive_keys(&self, salt: &[u8], info: &[u8], out: &mut [u8]) {
+        hkdf::expand(&hkdf::extract(salt, &self.0), info, out)
+    }
 }
 
 /// Represents the two parties in ECDH
@@ -329,6 +339,71 @@ impl ffi::CPtr for ElligatorSwift {
     fn as_c_ptr(&self) -> *const Self::Target { self.0.as_c_ptr() }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for ElligatorSwift {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.collect_str(self)
+        } else {
+            s.serialize_bytes(&self.to_array())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ElligatorSwift {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            d.deserialize_str(crate::serde_util::FromStrVisitor::new(
+                "a hex string representing a 64 byte ElligatorSwift encoding",
+            ))
+        } else {
+            d.deserialize_bytes(crate::serde_util::BytesVisitor::new(
+                "raw 64 bytes ElligatorSwift encoding",
+                |x| {
+                    x.try_into().map(ElligatorSwift::from_array).map_err(|_| Error::InvalidEllSwift)
+                },
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ElligatorSwiftSharedSecret {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            let mut buf = [0u8; 64];
+            s.serialize_str(crate::to_hex(&self.0, &mut buf).expect("fixed-size hex serialization"))
+        } else {
+            s.serialize_bytes(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ElligatorSwiftSharedSecret {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        fn parse(slice: &[u8]) -> Result<ElligatorSwiftSharedSecret, Error> {
+            slice
+                .try_into()
+                .map(ElligatorSwiftSharedSecret::from_secret_bytes)
+                .map_err(|_| Error::InvalidSharedSecret)
+        }
+
+        if d.is_human_readable() {
+            d.deserialize_str(crate::serde_util::HexVisitor::<_, 32>::new(
+                "a hex string representing 32 byte ElligatorSwift shared secret",
+                parse,
+            ))
+        } else {
+            d.deserialize_bytes(crate::serde_util::BytesVisitor::new(
+                "raw 32 bytes ElligatorSwift shared secret",
+                parse,
+            ))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
@@ -382,6 +457,19 @@ mod tests {
         );
         assert_eq!(pk, ElligatorSwiftSharedSecret([0xff; 32]));
     }
+
+    #[test]
+    fn derive_keys() {
+        // Computed with HKDF from Python's `cryptography` package.
+        let secret = crate::ellswift::ElligatorSwiftSharedSecret::from_secret_bytes([0xab; 32]);
+        let mut out = [0u8; 64];
+        secret.derive_keys(b"", b"bip324", &mut out);
+        assert_eq!(
+            out,
+            hex_lit::hex!("1f92642b1b40204f86bb47675782adc5dd4ecc7c7b7086b4ba14a3fabf04e51ebdb40f511813703fa5f076ba3e2340cca13420a3ff58c438f49bcab3dd90dd6e")
+        );
+    }
+
     #[test]
     #[cfg(all(not(secp256k1_fuzz), feature = "alloc"))]
     fn ellswift_ecdh_test() {
@@ -705,4 +793,49 @@ mod tests {
             assert_eq!(pk, test.key);
         }
     }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    fn serde() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        use crate::ellswift::ElligatorSwiftSharedSecret;
+
+        #[rustfmt::skip]
+        static BYTES: [u8; 64] = [
+            0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
+            0xc8, 0xc9, 0xca, 0xcb, 0xcc, 0xcd, 0xce, 0xcf,
+            0xd0, 0xd1, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7,
+            0xd8, 0xd9, 0xda, 0xdb, 0xdc, 0xdd, 0xde, 0xdf,
+            0xe0, 0xe1, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7,
+            0xe8, 0xe9, 0xea, 0xeb, 0xec, 0xed, 0xee, 0xef,
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+            0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+        ];
+        #[rustfmt::skip]
+        static SECRET_BYTES: [u8; 32] = [
+            0xe0, 0xe1, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7,
+            0xe8, 0xe9, 0xea, 0xeb, 0xec, 0xed, 0xee, 0xef,
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+            0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+        ];
+        static STR: &str = "c0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedf\
+                            e0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
+
+        let ell = ElligatorSwift::from_array(BYTES);
+        assert_tokens(&ell.compact(), &[Token::BorrowedBytes(&BYTES[..])]);
+        assert_tokens(&ell.compact(), &[Token::Bytes(&BYTES)]);
+        assert_tokens(&ell.compact(), &[Token::ByteBuf(&BYTES)]);
+        assert_tokens(&ell.readable(), &[Token::BorrowedStr(STR)]);
+        assert_tokens(&ell.readable(), &[Token::Str(STR)]);
+        assert_tokens(&ell.readable(), &[Token::String(STR)]);
+
+        let secret = ElligatorSwiftSharedSecret::from_secret_bytes(SECRET_BYTES);
+        assert_tokens(&secret.compact(), &[Token::BorrowedBytes(&SECRET_BYTES[..])]);
+        assert_tokens(&secret.compact(), &[Token::Bytes(&SECRET_BYTES)]);
+        assert_tokens(&secret.compact(), &[Token::ByteBuf(&SECRET_BYTES)]);
+        assert_tokens(&secret.readable(), &[Token::BorrowedStr(&STR[64..])]);
+        assert_tokens(&secret.readable(), &[Token::Str(&STR[64..])]);
+        assert_tokens(&secret.readable(), &[Token::String(&STR[64..])]);
+    }
 }
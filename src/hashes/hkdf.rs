@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! HKDF (RFC 5869) with HMAC-SHA256.
+
+use super::hmac::Hmac;
+use super::sha256;
+
+/// The length of the HMAC-SHA256 output.
+const HASH_SIZE: usize = 32;
+
+/// The largest output length of [`expand`].
+pub(crate) const MAX_OUTPUT_SIZE: usize = 255 * HASH_SIZE;
+
+/// Computes the pseudorandom key from the input keying material `ikm` and `salt`.
+pub(crate) fn extract(salt: &[u8], ikm: &[u8]) -> [u8; HASH_SIZE] {
+    let mut engine = Hmac::<sha256::HashEngine>::new(salt);
+    engine.input(ikm);
+    engine.finalize()
+}
+
+/// Fills `out` with output keying material derived from the pseudorandom key `prk` and `info`.
+///
+/// # Panics
+///
+/// If `out` is longer than [`MAX_OUTPUT_SIZE`].
+pub(crate) fn expand(prk: &[u8; HASH_SIZE], info: &[u8], out: &mut [u8]) {
+    assert!(out.len() <= MAX_OUTPUT_SIZE, "HKDF output is at most 8160 bytes");
+
+    let mut block = [0u8; HASH_SIZE];
+    for (i, chunk) in out.chunks_mut(HASH_SIZE).enumerate() {
+        let mut engine = Hmac::<sha256::HashEngine>::new(prk);
+        if i > 0 {
+            engine.input(&block);
+        }
+        engine.input(info);
+        engine.input(&[i as u8 + 1]);
+        block = engine.finalize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn rfc5869() {
+        // Test case 1.
+        let prk = extract(&hex!("000102030405060708090a0b0c"), &[0x0b; 22]);
+        assert_eq!(prk, hex!("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"));
+        let mut okm = [0u8; 42];
+        expand(&prk, &hex!("f0f1f2f3f4f5f6f7f8f9"), &mut okm);
+        assert_eq!(
+            okm,
+            hex!("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865")
+        );
+
+        // Test case 3, with empty salt and info.
+        let prk = extract(&[], &[0x0b; 22]);
+        assert_eq!(prk, hex!("19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04"));
+        let mut okm = [0u8; 42];
+        expand(&prk, &[], &mut okm);
+        assert_eq!(
+            okm,
+            hex!("8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8")
+        );
+    }
+}
@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! HMAC (RFC 2104) over any of the internal hash engines.
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+use core::fmt;
+
+use super::HashEngine;
+#[cfg(not(rust_secp_no_symbol_renaming))]
+use crate::ffi::hash as ffi;
+
+/// Largest block size of any supported hash engine.
+const MAX_BLOCK_SIZE: usize = 128;
+
+/// A streaming HMAC engine.
+pub(crate) struct Hmac<E: HashEngine> {
+    inner: E,
+    outer: E,
+}
+
+impl<E: HashEngine> Hmac<E> {
+    /// Creates a new HMAC engine keyed with `key`.
+    pub(crate) fn new(key: &[u8]) -> Self {
+        debug_assert!(E::BLOCK_SIZE <= MAX_BLOCK_SIZE);
+
+        let mut ipad = [0x36u8; MAX_BLOCK_SIZE];
+        let mut opad = [0x5cu8; MAX_BLOCK_SIZE];
+
+        if key.len() > E::BLOCK_SIZE {
+            let mut engine = E::default();
+            engine.input(key);
+            let digest = engine.finalize();
+            for (i, b) in digest.as_ref().iter().enumerate() {
+                ipad[i] ^= *b;
+                opad[i] ^= *b;
+            }
+        } else {
+            for (i, b) in key.iter().enumerate() {
+                ipad[i] ^= *b;
+                opad[i] ^= *b;
+            }
+        }
+
+        let mut inner = E::default();
+        let mut outer = E::default();
+        inner.input(&ipad[..E::BLOCK_SIZE]);
+        outer.input(&opad[..E::BLOCK_SIZE]);
+        Hmac { inner, outer }
+    }
+
+    /// Adds data to the HMAC engine.
+    pub(crate) fn input(&mut self, data: &[u8]) { self.inner.input(data) }
+
+    /// Finishes the computation and returns the MAC.
+    pub(crate) fn finalize(self) -> E::Digest {
+        let mut outer = self.outer;
+        outer.input(self.inner.finalize().as_ref());
+        outer.finalize()
+    }
+}
+
+/// An incremental HMAC-SHA256 engine, backed by the implementation in libsecp256k1.
+///
+/// # Examples
+///
+/// ```
+/// use secp256k1::hashes::HmacSha256;
+///
+/// let mut engine = HmacSha256::new(b"key");
+/// engine.input(b"message");
+/// assert_eq!(engine.finalize()[..4], [0x6e, 0x9e, 0xf2, 0x9b]);
+/// ```
+#[cfg(not(rust_secp_no_symbol_renaming))]
+#[derive(Clone)]
+pub struct HmacSha256(ffi::HmacSha256);
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl HmacSha256 {
+    /// Creates a new engine keyed with `key`.
+    pub fn new(key: &[u8]) -> HmacSha256 {
+        let mut state = ffi::HmacSha256::new();
+        unsafe { ffi::secp256k1_hmac_sha256_initialize(&mut state, key.as_ptr(), key.len()) };
+        HmacSha256(state)
+    }
+
+    /// Adds data to the engine.
+    pub fn input(&mut self, data: &[u8]) {
+        unsafe { ffi::secp256k1_hmac_sha256_write(&mut self.0, data.as_ptr(), data.len()) }
+    }
+
+    /// Finishes the computation and returns the MAC.
+    pub fn finalize(mut self) -> [u8; 32] {
+        let mut ret = [0u8; 32];
+        unsafe { ffi::secp256k1_hmac_sha256_finalize(&mut self.0, ret.as_mut_ptr()) };
+        ret
+    }
+}
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl Drop for HmacSha256 {
+    fn drop(&mut self) { unsafe { ffi::secp256k1_hmac_sha256_clear(&mut self.0) } }
+}
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl fmt::Debug for HmacSha256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HmacSha256").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+    use crate::hashes::sha512;
+
+    fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+        let mut engine = Hmac::<sha512::HashEngine>::new(key);
+        engine.input(data);
+        engine.finalize()
+    }
+
+    fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+        let mut engine = Hmac::<crate::hashes::sha256::HashEngine>::new(key);
+        engine.input(data);
+        let mac = engine.finalize();
+
+        #[cfg(not(rust_secp_no_symbol_renaming))]
+        {
+            let mut engine = HmacSha256::new(key);
+            engine.input(data);
+            assert_eq!(engine.finalize(), mac);
+        }
+        mac
+    }
+
+    #[test]
+    fn rfc4231_sha256() {
+        // Test case 1.
+        assert_eq!(
+            hmac_sha256(&[0x0b; 20], b"Hi There"),
+            hex!("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+        );
+        // Test case 2.
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+        );
+        // Test case 6, key larger than the block size.
+        assert_eq!(
+            hmac_sha256(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First"),
+            hex!("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+        );
+    }
+
+    #[test]
+    fn rfc4231_sha512() {
+        // Test case 1.
+        assert_eq!(
+            hmac_sha512(&[0x0b; 20], b"Hi There")[..],
+            hex!("87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854")[..],
+        );
+        // Test case 2.
+        assert_eq!(
+            hmac_sha512(b"Jefe", b"what do ya want for nothing?")[..],
+            hex!("164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737")[..],
+        );
+        // Test case 6, key larger than the block size.
+        assert_eq!(
+            hmac_sha512(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )[..],
+            hex!("80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598")[..],
+        );
+    }
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Keccak-256, as used by Ethereum.
+//!
+//! This is the original Keccak submission with `0x01` domain padding, not the standardized
+//! SHA3-256 which pads with `0x06`.
+
+/// The rate of Keccak-256, in bytes.
+const RATE: usize = 136;
+
+#[rustfmt::skip]
+const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+#[rustfmt::skip]
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+#[rustfmt::skip]
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Computes the Keccak-256 digest of `data`.
+pub(crate) fn hash(data: &[u8]) -> [u8; 32] {
+    let mut engine = HashEngine::default();
+    super::HashEngine::input(&mut engine, data);
+    super::HashEngine::finalize(engine)
+}
+
+/// The Keccak-f[1600] permutation.
+fn keccak_f(a: &mut [u64; 25]) {
+    for rc in RC.iter() {
+        // θ
+        let mut c = [0u64; 5];
+        for x in 0..5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[x + 5 * y] ^= d;
+            }
+        }
+
+        // ρ and π
+        let mut last = a[1];
+        for (&rho, &pi) in RHO.iter().zip(PI.iter()) {
+            let tmp = a[pi];
+            a[pi] = last.rotate_left(rho);
+            last = tmp;
+        }
+
+        // χ
+        for y in 0..5 {
+            let row = [a[5 * y], a[5 * y + 1], a[5 * y + 2], a[5 * y + 3], a[5 * y + 4]];
+            for x in 0..5 {
+                a[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // ι
+        a[0] ^= rc;
+    }
+}
+
+/// A streaming Keccak-256 engine.
+#[derive(Clone)]
+pub(crate) struct HashEngine {
+    state: [u64; 25],
+    buffer: [u8; RATE],
+    offset: usize,
+}
+
+impl Default for HashEngine {
+    fn default() -> Self { HashEngine { state: [0; 25], buffer: [0; RATE], offset: 0 } }
+}
+
+impl HashEngine {
+    fn absorb(&mut self) {
+        for (s, chunk) in self.state.iter_mut().zip(self.buffer.chunks_exact(8)) {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            *s ^= u64::from_le_bytes(word);
+        }
+        keccak_f(&mut self.state);
+        self.offset = 0;
+    }
+}
+
+impl super::HashEngine for HashEngine {
+    const BLOCK_SIZE: usize = RATE;
+
+    type Digest = [u8; 32];
+
+    fn input(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let n = core::cmp::min(RATE - self.offset, data.len());
+            self.buffer[self.offset..self.offset + n].copy_from_slice(&data[..n]);
+            self.offset += n;
+            data = &data[n..];
+            if self.offset == RATE {
+                self.absorb();
+            }
+        }
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        for b in self.buffer[self.offset..].iter_mut() {
+            *b = 0;
+        }
+        self.buffer[self.offset] ^= 0x01;
+        self.buffer[RATE - 1] ^= 0x80;
+        self.absorb();
+
+        let mut ret = [0u8; 32];
+        for (chunk, s) in ret.chunks_exact_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&s.to_le_bytes());
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+    use crate::hashes::HashEngine as _;
+
+    #[test]
+    fn vectors() {
+        assert_eq!(
+            hash(b""),
+            hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        );
+        assert_eq!(
+            hash(b"abc"),
+            hex!("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+        );
+    }
+
+    #[test]
+    fn streaming_matches_oneshot() {
+        let data = [0xa5u8; 1000];
+        for split in [0, 1, 135, 136, 137, 272, 999] {
+            let mut engine = HashEngine::default();
+            engine.input(&data[..split]);
+            engine.input(&data[split..]);
+            assert_eq!(engine.finalize(), hash(&data));
+        }
+    }
+}
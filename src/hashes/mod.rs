@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Hash functions.
+//!
+//! [`Sha256`], [`HmacSha256`] and [`Rfc6979HmacSha256`] expose the implementations that
+//! libsecp256k1 already contains, so users of this crate do not need to link a second SHA-256.
+//! They are not available with `rust_secp_no_symbol_renaming`, since a system libsecp256k1 does
+//! not export these functions.
+//! The other hash functions are minimal implementations used internally, so that protocols
+//! layered on top of the curve operations (for example BIP-32 style derivation) do not require
+//! pulling in an additional hashing dependency.
+
+pub(crate) mod hkdf;
+pub(crate) mod hmac;
+pub(crate) mod keccak256;
+#[cfg(not(rust_secp_no_symbol_renaming))]
+mod rfc6979;
+pub(crate) mod sha256;
+pub(crate) mod sha512;
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+pub use self::hmac::HmacSha256;
+#[cfg(not(rust_secp_no_symbol_renaming))]
+pub use self::rfc6979::Rfc6979HmacSha256;
+#[cfg(not(rust_secp_no_symbol_renaming))]
+pub use self::sha256::Sha256;
+
+/// A streaming hash engine.
+pub(crate) trait HashEngine: Default {
+    /// The internal block size of the hash function, in bytes.
+    const BLOCK_SIZE: usize;
+
+    /// The output of the hash function.
+    type Digest: AsRef<[u8]>;
+
+    /// Adds data to the hash engine.
+    fn input(&mut self, data: &[u8]);
+
+    /// Finishes the computation and returns the digest.
+    fn finalize(self) -> Self::Digest;
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! The HMAC-SHA256 deterministic random bit generator of RFC 6979 section 3.2.
+
+use core::fmt;
+
+use crate::ffi::hash as ffi;
+
+/// The RFC 6979 HMAC-SHA256 DRBG, backed by the implementation in libsecp256k1.
+///
+/// This is the generator libsecp256k1 uses for deterministic ECDSA nonces, seeded with the
+/// secret key followed by the message hash and optional extra data.
+///
+/// # Examples
+///
+/// ```
+/// use secp256k1::hashes::{Rfc6979HmacSha256, Sha256};
+///
+/// let mut seed = [0u8; 64];
+/// seed[31] = 1;
+/// seed[32..].copy_from_slice(&Sha256::hash(b"Satoshi Nakamoto"));
+///
+/// let mut nonce = [0u8; 32];
+/// Rfc6979HmacSha256::new(&seed).generate(&mut nonce);
+/// assert_eq!(nonce[..4], [0x8f, 0x8a, 0x27, 0x6c]);
+/// ```
+#[derive(Clone)]
+pub struct Rfc6979HmacSha256(ffi::Rfc6979HmacSha256);
+
+impl Rfc6979HmacSha256 {
+    /// Creates a new generator seeded with `key`.
+    pub fn new(key: &[u8]) -> Rfc6979HmacSha256 {
+        let mut state = ffi::Rfc6979HmacSha256::new();
+        unsafe {
+            ffi::secp256k1_rfc6979_hmac_sha256_initialize(&mut state, key.as_ptr(), key.len())
+        };
+        Rfc6979HmacSha256(state)
+    }
+
+    /// Fills `out` with the next output of the generator.
+    ///
+    /// Every call after the first updates the state as for a rejected candidate (RFC 6979 section
+    /// 3.2 step h.3) before generating, so splitting a request across calls changes the output.
+    pub fn generate(&mut self, out: &mut [u8]) {
+        unsafe {
+            ffi::secp256k1_rfc6979_hmac_sha256_generate(&mut self.0, out.as_mut_ptr(), out.len())
+        }
+    }
+}
+
+impl Drop for Rfc6979HmacSha256 {
+    fn drop(&mut self) { unsafe { ffi::secp256k1_rfc6979_hmac_sha256_clear(&mut self.0) } }
+}
+
+impl fmt::Debug for Rfc6979HmacSha256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rfc6979HmacSha256").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn generate() {
+        // Computed with a Python implementation of RFC 6979 section 3.2.
+        let mut seed = [0u8; 64];
+        for (i, byte) in seed.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut rng = Rfc6979HmacSha256::new(&seed);
+        let mut out = [0u8; 32];
+        rng.generate(&mut out);
+        assert_eq!(out, hex!("acb4c0527346343f45266a99a5ea86e9493b69635f509ed6871d4c871f93613b"));
+        let mut out = [0u8; 40];
+        rng.generate(&mut out);
+        assert_eq!(
+            out,
+            hex!(
+                "605527fd6e0367631059fbc871d16d1c1944a4faa9a40c68ed4e301ac351b75095a94c8f5157f4c0"
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn matches_ecdsa_nonce() {
+        use crate::{ecdsa, Message, PublicKey, SecretKey};
+
+        let sk = SecretKey::from_secret_bytes([0x11; 32]).unwrap();
+        let msg = [0x22; 32];
+        let mut seed = [0u8; 64];
+        seed[..32].copy_from_slice(&sk.to_secret_bytes());
+        seed[32..].copy_from_slice(&msg);
+        let mut k = [0u8; 32];
+        Rfc6979HmacSha256::new(&seed).generate(&mut k);
+
+        // The r value of the signature is the x coordinate of k*G.
+        let k = SecretKey::from_secret_bytes(k).unwrap();
+        let r = PublicKey::from_secret_key(&k).x_only_public_key().0.serialize();
+        let sig = ecdsa::sign(Message::from_digest(msg), &sk).serialize_compact();
+        assert_eq!(sig[..32], r);
+    }
+}
@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! SHA-256 (FIPS 180-4).
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+use core::fmt;
+
+#[cfg(rust_secp_no_symbol_renaming)]
+pub(crate) use self::fallback::HashEngine;
+#[cfg(not(rust_secp_no_symbol_renaming))]
+use crate::ffi::hash as ffi;
+
+/// Computes the SHA-256 digest of `data`.
+pub(crate) fn hash(data: &[u8]) -> [u8; 32] {
+    let mut engine = HashEngine::default();
+    engine.input(data);
+    engine.finalize()
+}
+
+/// Returns an engine for the BIP-340 style tagged hash `SHA256(SHA256(tag) || SHA256(tag) || x)`,
+/// with the tag prefix already written.
+pub(crate) fn tagged_engine(tag: &[u8]) -> HashEngine {
+    let tag_hash = hash(tag);
+    let mut engine = HashEngine::default();
+    engine.input(&tag_hash);
+    engine.input(&tag_hash);
+    engine
+}
+
+/// The streaming SHA-256 engine used internally.
+#[cfg(not(rust_secp_no_symbol_renaming))]
+pub(crate) type HashEngine = Sha256;
+
+/// An incremental SHA-256 hash engine, backed by the implementation in libsecp256k1.
+///
+/// # Examples
+///
+/// ```
+/// use secp256k1::hashes::Sha256;
+///
+/// let mut engine = Sha256::new();
+/// engine.input(b"a");
+/// engine.input(b"bc");
+/// assert_eq!(engine.finalize(), Sha256::hash(b"abc"));
+/// ```
+#[cfg(not(rust_secp_no_symbol_renaming))]
+#[derive(Clone)]
+pub struct Sha256(ffi::Sha256);
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl Sha256 {
+    /// Creates a new engine.
+    pub fn new() -> Sha256 {
+        let mut state = ffi::Sha256::new();
+        unsafe { ffi::secp256k1_sha256_initialize(&mut state) };
+        Sha256(state)
+    }
+
+    /// Computes the SHA-256 digest of `data`.
+    pub fn hash(data: &[u8]) -> [u8; 32] {
+        let mut engine = Sha256::new();
+        engine.input(data);
+        engine.finalize()
+    }
+
+    /// Adds data to the engine.
+    pub fn input(&mut self, data: &[u8]) {
+        unsafe { ffi::secp256k1_sha256_write(&mut self.0, data.as_ptr(), data.len()) }
+    }
+
+    /// Finishes the computation and returns the digest.
+    pub fn finalize(mut self) -> [u8; 32] {
+        let mut ret = [0u8; 32];
+        unsafe { ffi::secp256k1_sha256_finalize(&mut self.0, ret.as_mut_ptr()) };
+        ret
+    }
+}
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl Default for Sha256 {
+    fn default() -> Self { Sha256::new() }
+}
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl Drop for Sha256 {
+    fn drop(&mut self) { unsafe { ffi::secp256k1_sha256_clear(&mut self.0) } }
+}
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl fmt::Debug for Sha256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sha256").finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(rust_secp_no_symbol_renaming))]
+impl super::HashEngine for Sha256 {
+    const BLOCK_SIZE: usize = 64;
+
+    type Digest = [u8; 32];
+
+    fn input(&mut self, data: &[u8]) { Sha256::input(self, data) }
+
+    fn finalize(self) -> [u8; 32] { Sha256::finalize(self) }
+}
+
+/// A pure Rust SHA-256 engine, used when libsecp256k1's implementation is not exported.
+#[cfg(rust_secp_no_symbol_renaming)]
+mod fallback {
+    const BLOCK_SIZE: usize = 64;
+
+    #[rustfmt::skip]
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+        0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+        0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+        0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+
+    #[rustfmt::skip]
+    const IV: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+
+    /// A streaming SHA-256 engine.
+    #[derive(Clone)]
+    pub(crate) struct HashEngine {
+        state: [u32; 8],
+        buffer: [u8; BLOCK_SIZE],
+        length: u64,
+    }
+
+    impl Default for HashEngine {
+        fn default() -> Self { HashEngine { state: IV, buffer: [0; BLOCK_SIZE], length: 0 } }
+    }
+
+    impl HashEngine {
+        /// Adds data to the engine.
+        pub(crate) fn input(&mut self, mut data: &[u8]) {
+            while !data.is_empty() {
+                let offset = (self.length % BLOCK_SIZE as u64) as usize;
+                let n = core::cmp::min(BLOCK_SIZE - offset, data.len());
+                self.buffer[offset..offset + n].copy_from_slice(&data[..n]);
+                self.length += n as u64;
+                data = &data[n..];
+                if offset + n == BLOCK_SIZE {
+                    self.compress();
+                }
+            }
+        }
+
+        /// Finishes the computation and returns the digest.
+        pub(crate) fn finalize(mut self) -> [u8; 32] {
+            let bit_length = self.length * 8;
+
+            self.input(&[0x80]);
+            while self.length % BLOCK_SIZE as u64 != BLOCK_SIZE as u64 - 8 {
+                self.input(&[0x00]);
+            }
+            self.input(&bit_length.to_be_bytes());
+
+            let mut ret = [0u8; 32];
+            for (chunk, s) in ret.chunks_exact_mut(4).zip(self.state.iter()) {
+                chunk.copy_from_slice(&s.to_be_bytes());
+            }
+            ret
+        }
+
+        fn compress(&mut self) {
+            let mut w = [0u32; 64];
+            for (i, chunk) in self.buffer.chunks_exact(4).enumerate() {
+                let mut word = [0u8; 4];
+                word.copy_from_slice(chunk);
+                w[i] = u32::from_be_bytes(word);
+            }
+            for i in 16..64 {
+                let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+                let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+                w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+            }
+
+            let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+            for i in 0..64 {
+                let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+                let ch = (e & f) ^ (!e & g);
+                let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+                let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+                let maj = (a & b) ^ (a & c) ^ (b & c);
+                let t2 = s0.wrapping_add(maj);
+
+                h = g;
+                g = f;
+                f = e;
+                e = d.wrapping_add(t1);
+                d = c;
+                c = b;
+                b = a;
+                a = t1.wrapping_add(t2);
+            }
+
+            for (s, v) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+                *s = s.wrapping_add(*v);
+            }
+        }
+    }
+
+    impl super::super::HashEngine for HashEngine {
+        const BLOCK_SIZE: usize = BLOCK_SIZE;
+
+        type Digest = [u8; 32];
+
+        fn input(&mut self, data: &[u8]) { HashEngine::input(self, data) }
+
+        fn finalize(self) -> [u8; 32] { HashEngine::finalize(self) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn fips_180_vectors() {
+        assert_eq!(
+            hash(b""),
+            hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        );
+        assert_eq!(
+            hash(b"abc"),
+            hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        );
+        assert_eq!(
+            hash(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            hex!("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+        );
+    }
+
+    #[test]
+    fn tagged_hash() {
+        let mut engine = tagged_engine(b"BIP0340/challenge");
+        engine.input(b"abc");
+        assert_eq!(
+            engine.finalize(),
+            hex!("770a5b7e7c304bbcc3ea107343ff951dd404312ef418db0c3b94e2ebfbb50087"),
+        );
+    }
+
+    #[test]
+    fn streaming_matches_oneshot() {
+        let data = [0xa5u8; 1000];
+        for split in [0, 1, 55, 56, 63, 64, 65, 999] {
+            let mut engine = HashEngine::default();
+            engine.input(&data[..split]);
+            engine.input(&data[split..]);
+            assert_eq!(engine.finalize(), hash(&data));
+        }
+    }
+}
@@ -0,0 +1,462 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! JSON Web Keys (RFC 7517) on the secp256k1 curve (RFC 8812).
+//!
+//! A secp256k1 JWK is a JSON object with the members `"kty": "EC"`, `"crv": "secp256k1"`, the
+//! base64url-encoded coordinates `x` and `y` of the public key and, for private keys, the
+//! base64url-encoded secret key `d`.
+
+use core::{fmt, mem};
+
+use serde::de::{self, IgnoredAny};
+use serde::ser::SerializeMap;
+
+use crate::hashes::sha256;
+use crate::serde_util::Base64UrlVisitor;
+use crate::{base64, constants, Error, Keypair, PublicKey, SecretKey};
+
+/// The JWK key type of elliptic curve keys.
+const KTY: &str = "EC";
+/// The JWK curve name of secp256k1.
+const CRV: &str = "secp256k1";
+/// The length of a base64url-encoded 32-byte field element.
+const ENCODED_SIZE: usize = base64::url_safe_encoded_len(constants::SECRET_KEY_SIZE);
+
+/// A secp256k1 JSON Web Key, holding a public key and optionally its secret key.
+///
+/// # Serde support
+///
+/// Serializes as the JWK object with the members `kty`, `crv`, `x`, `y` and, if the secret key is
+/// present, `d`. Deserialization ignores unknown members (such as `kid` or `use`), rejects points
+/// that are not on the curve and secret keys that do not match the public key.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "rand", feature = "std"))] {
+/// use secp256k1::{rand, Jwk, Keypair};
+///
+/// let keypair = Keypair::new(&mut rand::rng());
+/// let jwk = Jwk::from(keypair);
+/// assert_eq!(jwk.keypair(), Some(keypair));
+/// assert_eq!(jwk.to_public().thumbprint(), jwk.thumbprint());
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Jwk {
+    public_key: PublicKey,
+    secret_key: Option<SecretKey>,
+}
+
+impl Jwk {
+    /// Returns the public key.
+    #[inline]
+    pub fn public_key(&self) -> PublicKey { self.public_key }
+
+    /// Returns the secret key, if this is a private key.
+    #[inline]
+    pub fn secret_key(&self) -> Option<SecretKey> { self.secret_key }
+
+    /// Returns the keypair, if this is a private key.
+    #[inline]
+    pub fn keypair(&self) -> Option<Keypair> {
+        self.secret_key.map(|sk| Keypair::from_secret_key(&sk))
+    }
+
+    /// Returns the public JWK, without the secret key.
+    #[inline]
+    pub fn to_public(&self) -> Jwk { Jwk { public_key: self.public_key, secret_key: None } }
+
+    /// Computes the RFC 7638 thumbprint, the SHA-256 hash of the canonical JSON form of the
+    /// public key.
+    ///
+    /// The thumbprint only depends on the public key, so it is the same for a private key and its
+    /// public JWK.
+    pub fn thumbprint(&self) -> [u8; 32] {
+        let (x, y) = self.coordinates();
+        let mut x_buf = [0u8; ENCODED_SIZE];
+        let mut y_buf = [0u8; ENCODED_SIZE];
+
+        let mut engine = sha256::HashEngine::default();
+        engine.input(br#"{"crv":""#);
+        engine.input(CRV.as_bytes());
+        engine.input(br#"","kty":""#);
+        engine.input(KTY.as_bytes());
+        engine.input(br#"","x":""#);
+        engine.input(encode(&x, &mut x_buf).as_bytes());
+        engine.input(br#"","y":""#);
+        engine.input(encode(&y, &mut y_buf).as_bytes());
+        engine.input(br#""}"#);
+        engine.finalize()
+    }
+
+    /// Returns the big-endian coordinates of the public key.
+    fn coordinates(&self) -> ([u8; 32], [u8; 32]) {
+        let serialized = self.public_key.serialize_uncompressed();
+        let mut x = [0u8; 32];
+        let mut y = [0u8; 32];
+        x.copy_from_slice(&serialized[1..33]);
+        y.copy_from_slice(&serialized[33..]);
+        (x, y)
+    }
+}
+
+impl From<PublicKey> for Jwk {
+    #[inline]
+    fn from(public_key: PublicKey) -> Self { Jwk { public_key, secret_key: None } }
+}
+
+impl From<SecretKey> for Jwk {
+    #[inline]
+    fn from(secret_key: SecretKey) -> Self {
+        Jwk { public_key: secret_key.public_key(), secret_key: Some(secret_key) }
+    }
+}
+
+impl From<Keypair> for Jwk {
+    #[inline]
+    fn from(keypair: Keypair) -> Self {
+        Jwk { public_key: keypair.public_key(), secret_key: Some(keypair.secret_key()) }
+    }
+}
+
+impl From<Jwk> for PublicKey {
+    #[inline]
+    fn from(jwk: Jwk) -> Self { jwk.public_key }
+}
+
+impl TryFrom<Jwk> for SecretKey {
+    type Error = Error;
+
+    /// Returns [`Error::InvalidSecretKey`] if the JWK is a public key.
+    #[inline]
+    fn try_from(jwk: Jwk) -> Result<Self, Error> { jwk.secret_key.ok_or(Error::InvalidSecretKey) }
+}
+
+impl TryFrom<Jwk> for Keypair {
+    type Error = Error;
+
+    /// Returns [`Error::InvalidSecretKey`] if the JWK is a public key.
+    #[inline]
+    fn try_from(jwk: Jwk) -> Result<Self, Error> { jwk.keypair().ok_or(Error::InvalidSecretKey) }
+}
+
+/// Encodes a 32-byte value as base64url into `buf`.
+fn encode<'a>(bytes: &[u8; 32], buf: &'a mut [u8; ENCODED_SIZE]) -> &'a str {
+    base64::encode_url_safe(bytes, buf).expect("buffer is large enough")
+}
+
+impl serde::Serialize for Jwk {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let (x, y) = self.coordinates();
+        let mut buf = [0u8; ENCODED_SIZE];
+
+        let mut map = s.serialize_map(Some(if self.secret_key.is_some() { 5 } else { 4 }))?;
+        map.serialize_entry("kty", KTY)?;
+        map.serialize_entry("crv", CRV)?;
+        map.serialize_entry("x", encode(&x, &mut buf))?;
+        map.serialize_entry("y", encode(&y, &mut buf))?;
+        if let Some(sk) = self.secret_key {
+            map.serialize_entry("d", encode(&sk.to_secret_bytes(), &mut buf))?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Jwk {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_map(JwkVisitor)
+    }
+}
+
+/// The JWK members this crate interprets.
+enum Field {
+    Kty,
+    Crv,
+    X,
+    Y,
+    D,
+    Other,
+}
+
+impl<'de> serde::Deserialize<'de> for Field {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JWK member name")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Field, E> {
+                Ok(match v {
+                    "kty" => Field::Kty,
+                    "crv" => Field::Crv,
+                    "x" => Field::X,
+                    "y" => Field::Y,
+                    "d" => Field::D,
+                    _ => Field::Other,
+                })
+            }
+        }
+
+        d.deserialize_identifier(Visitor)
+    }
+}
+
+/// A string member that must have one particular value.
+struct Expected(&'static str);
+
+impl<'de> de::DeserializeSeed<'de> for Expected {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, d: D) -> Result<(), D::Error> {
+        d.deserialize_str(self)
+    }
+}
+
+impl de::Visitor<'_> for Expected {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "\"{}\"", self.0) }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+        if v == self.0 {
+            Ok(())
+        } else {
+            Err(E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+}
+
+/// A base64url-encoded 32-byte member.
+struct Bytes32([u8; 32]);
+
+impl<'de> serde::Deserialize<'de> for Bytes32 {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_str(Base64UrlVisitor::<_, 32>::new(
+            "a base64url-encoded 32-byte value",
+            |bytes: &[u8]| {
+                bytes.try_into().map(Bytes32).map_err(|_| "the value is not 32 bytes long")
+            },
+        ))
+    }
+}
+
+struct JwkVisitor;
+
+impl<'de> de::Visitor<'de> for JwkVisitor {
+    type Value = Jwk;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a secp256k1 JSON Web Key")
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Jwk, A::Error> {
+        let (mut kty, mut crv) = (false, false);
+        let (mut x, mut y, mut d) = (None, None, None);
+        while let Some(field) = map.next_key()? {
+            // Every member may appear only once.
+            let seen = match field {
+                Field::Kty => {
+                    map.next_value_seed(Expected(KTY))?;
+                    mem::replace(&mut kty, true)
+                }
+                Field::Crv => {
+                    map.next_value_seed(Expected(CRV))?;
+                    mem::replace(&mut crv, true)
+                }
+                Field::X => x.replace(map.next_value::<Bytes32>()?.0).is_some(),
+                Field::Y => y.replace(map.next_value::<Bytes32>()?.0).is_some(),
+                Field::D => d.replace(map.next_value::<Bytes32>()?.0).is_some(),
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                    false
+                }
+            };
+            if seen {
+                return Err(de::Error::custom("duplicate JWK member"));
+            }
+        }
+
+        if !kty {
+            return Err(de::Error::missing_field("kty"));
+        }
+        if !crv {
+            return Err(de::Error::missing_field("crv"));
+        }
+        let x = x.ok_or_else(|| de::Error::missing_field("x"))?;
+        let y = y.ok_or_else(|| de::Error::missing_field("y"))?;
+
+        let mut serialized = [0u8; constants::UNCOMPRESSED_PUBLIC_KEY_SIZE];
+        serialized[0] = 0x04;
+        serialized[1..33].copy_from_slice(&x);
+        serialized[33..].copy_from_slice(&y);
+        let public_key = PublicKey::from_byte_array_uncompressed(serialized)
+            .map_err(|_| de::Error::custom("the JWK point is not on the secp256k1 curve"))?;
+
+        let secret_key = match d {
+            Some(d) => {
+                let sk = SecretKey::from_secret_bytes(d)
+                    .map_err(|_| de::Error::custom("the JWK secret key is out of range"))?;
+                if sk.public_key() != public_key {
+                    return Err(de::Error::custom(
+                        "the JWK secret key does not match its public key",
+                    ));
+                }
+                Some(sk)
+            }
+            None => None,
+        };
+        Ok(Jwk { public_key, secret_key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    const X: &str = "8YVKk-tPtatFYuFkOKbgUNmwPgvVs_GsjSBIqZQkwm0";
+    const Y: &str = "GYN66ta5-hc5u3qtPOUVCsR6vw_sa1GuJLVixpd75d8";
+    const D: &str = "XV1RRxfT_e4wN4KOXKHfg-j9Ik00Sx0EO8EGQs-qkf4";
+
+    fn secret_key() -> SecretKey {
+        SecretKey::from_secret_bytes(hex!(
+            "5d5d514717d3fdee3037828e5ca1df83e8fd224d344b1d043bc10642cfaa91fe"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn conversions() {
+        let sk = secret_key();
+        let jwk = Jwk::from(sk);
+        assert_eq!(jwk, Jwk::from(Keypair::from_secret_key(&sk)));
+        assert_eq!(jwk.public_key(), sk.public_key());
+        assert_eq!(SecretKey::try_from(jwk), Ok(sk));
+        assert_eq!(Keypair::try_from(jwk), Ok(Keypair::from_secret_key(&sk)));
+
+        let public = jwk.to_public();
+        assert_eq!(public, Jwk::from(sk.public_key()));
+        assert_eq!(PublicKey::from(public), sk.public_key());
+        assert_eq!(public.secret_key(), None);
+        assert_eq!(SecretKey::try_from(public), Err(Error::InvalidSecretKey));
+        assert_eq!(Keypair::try_from(public), Err(Error::InvalidSecretKey));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn thumbprint() {
+        let jwk = Jwk::from(secret_key());
+        let expected = hex!("3e7415f616c21a87e419c6a0363394fc2ef7b2dfbaf7c877b3efeefadb59d202");
+        assert_eq!(jwk.thumbprint(), expected);
+        assert_eq!(jwk.to_public().thumbprint(), expected);
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn serde() {
+        let jwk = Jwk::from(secret_key());
+        #[rustfmt::skip]
+        assert_tokens(&jwk, &[
+            Token::Map { len: Some(5) },
+            Token::Str("kty"), Token::Str("EC"),
+            Token::Str("crv"), Token::Str("secp256k1"),
+            Token::Str("x"), Token::Str(X),
+            Token::Str("y"), Token::Str(Y),
+            Token::Str("d"), Token::Str(D),
+            Token::MapEnd,
+        ]);
+        #[rustfmt::skip]
+        assert_tokens(&jwk.to_public(), &[
+            Token::Map { len: Some(4) },
+            Token::Str("kty"), Token::Str("EC"),
+            Token::Str("crv"), Token::Str("secp256k1"),
+            Token::Str("x"), Token::Str(X),
+            Token::Str("y"), Token::Str(Y),
+            Token::MapEnd,
+        ]);
+
+        // Member order does not matter and unknown members are ignored.
+        #[rustfmt::skip]
+        serde_test::assert_de_tokens(&jwk.to_public(), &[
+            Token::Map { len: None },
+            Token::Str("y"), Token::Str(Y),
+            Token::Str("kid"), Token::Str("key-1"),
+            Token::Str("x"), Token::Str(X),
+            Token::Str("crv"), Token::Str("secp256k1"),
+            Token::Str("key_ops"), Token::Seq { len: Some(1) }, Token::Str("verify"), Token::SeqEnd,
+            Token::Str("kty"), Token::Str("EC"),
+            Token::MapEnd,
+        ]);
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn serde_invalid() {
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("kty"), Token::Str("OKP"),
+        ], "invalid value: string \"OKP\", expected \"EC\"");
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("crv"), Token::Str("P-256"),
+        ], "invalid value: string \"P-256\", expected \"secp256k1\"");
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("kty"), Token::Str("EC"),
+            Token::Str("crv"), Token::Str("secp256k1"),
+            Token::Str("x"), Token::Str(X),
+            Token::MapEnd,
+        ], "missing field `y`");
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("x"), Token::Str(X),
+            Token::Str("x"), Token::Str(X),
+        ], "duplicate JWK member");
+        // A 31-byte coordinate.
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("x"), Token::Str("8YVKk-tPtatFYuFkOKbgUNmwPgvVs_GsjSBIqZQkwg"),
+        ], "the value is not 32 bytes long");
+        // Padded base64url is not allowed in JWKs.
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("x"), Token::Str("8YVKk-tPtatFYuFkOKbgUNmwPgvVs_GsjSBIqZQkwm0="),
+        ], "invalid value: invalid base64url string, expected a base64url-encoded 32-byte value");
+        // A point with equal coordinates is not on the curve.
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("kty"), Token::Str("EC"),
+            Token::Str("crv"), Token::Str("secp256k1"),
+            Token::Str("x"), Token::Str(X),
+            Token::Str("y"), Token::Str(X),
+            Token::MapEnd,
+        ], "the JWK point is not on the secp256k1 curve");
+
+        #[rustfmt::skip]
+        assert_de_tokens_error::<Jwk>(&[
+            Token::Map { len: None },
+            Token::Str("kty"), Token::Str("EC"),
+            Token::Str("crv"), Token::Str("secp256k1"),
+            Token::Str("x"), Token::Str(X),
+            Token::Str("y"), Token::Str(Y),
+            Token::Str("d"), Token::Str("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAE"),
+            Token::MapEnd,
+        ], "the JWK secret key does not match its public key");
+    }
+}
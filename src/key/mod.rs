@@ -3,7 +3,11 @@
 //! Public and secret keys.
 //!
 
+mod der;
+#[cfg(feature = "serde")]
+mod jwk;
 mod secret;
+pub mod wif;
 
 use core::ops::BitXor;
 use core::{fmt, ptr, str};
@@ -13,6 +17,9 @@ use arbitrary::{Arbitrary, Unstructured};
 #[cfg(feature = "serde")]
 use serde::ser::SerializeTuple;
 
+pub use self::der::KeyDecodeError;
+#[cfg(feature = "serde")]
+pub use self::jwk::Jwk;
 pub use self::secret::SecretKey;
 use crate::ellswift::ElligatorSwift;
 use crate::ffi::types::c_uint;
@@ -234,6 +241,17 @@ impl PublicKey {
         ret
     }
 
+    /// Returns the Ethereum address of this key.
+    ///
+    /// This is the last 20 bytes of the Keccak-256 hash of the uncompressed serialization,
+    /// without its `0x04` prefix byte.
+    pub fn to_eth_address(&self) -> [u8; 20] {
+        let hash = crate::hashes::keccak256::hash(&self.serialize_uncompressed()[1..]);
+        let mut ret = [0u8; 20];
+        ret.copy_from_slice(&hash[12..]);
+        ret
+    }
+
     #[inline(always)]
     fn serialize_internal(&self, ret: &mut [u8], flag: c_uint) {
         let mut ret_len = ret.len();
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! The Wallet Import Format (WIF) of Bitcoin secret keys.
+//!
+//! A WIF string is the Base58Check encoding of a network prefix byte, the 32-byte secret key and,
+//! for keys whose public key is used in compressed form, a trailing `0x01` byte.
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::alloc::string::{String, ToString};
+use crate::hashes::sha256;
+use crate::{base58, constants, SecretKey};
+
+/// The WIF prefix of mainnet keys.
+const MAINNET_PREFIX: u8 = 0x80;
+/// The WIF prefix of testnet, signet and regtest keys.
+const TESTNET_PREFIX: u8 = 0xef;
+/// The trailing byte of keys with compressed public keys.
+const COMPRESSED_FLAG: u8 = 0x01;
+/// The length of the Base58Check checksum.
+const CHECKSUM_SIZE: usize = 4;
+/// The length of a decoded WIF with compression flag and checksum.
+const MAX_WIF_SIZE: usize = 1 + constants::SECRET_KEY_SIZE + 1 + CHECKSUM_SIZE;
+
+/// The kind of Bitcoin network a WIF-encoded key belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Network {
+    /// Bitcoin mainnet.
+    Mainnet,
+    /// Bitcoin testnet, signet and regtest, which share the same prefix.
+    Testnet,
+}
+
+impl Network {
+    #[cfg(feature = "alloc")]
+    fn prefix(self) -> u8 {
+        match self {
+            Network::Mainnet => MAINNET_PREFIX,
+            Network::Testnet => TESTNET_PREFIX,
+        }
+    }
+}
+
+/// An error parsing a WIF-encoded secret key.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum WifError {
+    /// The string contains a character outside the base58 alphabet.
+    InvalidBase58,
+    /// The decoded data has a length other than 37 or 38 bytes.
+    InvalidLength,
+    /// The Base58Check checksum does not match.
+    InvalidChecksum,
+    /// The network prefix byte is not known.
+    UnknownNetwork(u8),
+    /// The byte after the secret key is not the compression flag `0x01`.
+    InvalidCompressionFlag(u8),
+    /// The key is zero or not below the curve order.
+    InvalidSecretKey,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WifError {}
+
+impl fmt::Display for WifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            WifError::InvalidBase58 => f.write_str("invalid base58 character in WIF"),
+            WifError::InvalidLength => f.write_str("invalid WIF length"),
+            WifError::InvalidChecksum => f.write_str("invalid WIF checksum"),
+            WifError::UnknownNetwork(prefix) => write!(f, "unknown WIF prefix {:#04x}", prefix),
+            WifError::InvalidCompressionFlag(flag) =>
+                write!(f, "invalid WIF compression flag {:#04x}", flag),
+            WifError::InvalidSecretKey => f.write_str("WIF key is out of range"),
+        }
+    }
+}
+
+impl SecretKey {
+    /// Encodes the secret key in the Wallet Import Format.
+    ///
+    /// `compressed` records whether the key's public key is used in compressed form, which
+    /// determines the addresses a wallet derives from the imported key.
+    ///
+    /// The returned string contains the secret key, treat it with the same care.
+    #[cfg(feature = "alloc")]
+    pub fn to_wif(&self, network: Network, compressed: bool) -> String {
+        let mut data = [0u8; MAX_WIF_SIZE];
+        data[0] = network.prefix();
+        data[1..33].copy_from_slice(&self.to_secret_bytes());
+        let len = if compressed {
+            data[33] = COMPRESSED_FLAG;
+            34
+        } else {
+            33
+        };
+        let checksum = checksum(&data[..len]);
+        data[len..len + CHECKSUM_SIZE].copy_from_slice(&checksum);
+
+        let mut buf = [0u8; 2 * MAX_WIF_SIZE];
+        base58::encode(&data[..len + CHECKSUM_SIZE], &mut buf)
+            .expect("buffer is large enough")
+            .to_string()
+    }
+
+    /// Parses a secret key in the Wallet Import Format.
+    ///
+    /// Returns the key, its network and whether its public key is used in compressed form.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifError::InvalidChecksum`] if the string is well-formed but mistyped, and
+    /// [`WifError::InvalidSecretKey`] if it encodes a key outside the valid range.
+    pub fn from_wif(wif: &str) -> Result<(SecretKey, Network, bool), WifError> {
+        // Room for one byte too many, to tell overlong data from base58 errors.
+        let mut data = [0u8; MAX_WIF_SIZE + 1];
+        let len = base58::decode(wif, &mut data).map_err(|e| match e {
+            base58::DecodeError::InvalidCharacter => WifError::InvalidBase58,
+            base58::DecodeError::TooLong => WifError::InvalidLength,
+        })?;
+        if len != MAX_WIF_SIZE && len != MAX_WIF_SIZE - 1 {
+            return Err(WifError::InvalidLength);
+        }
+        let (payload, check) = data[..len].split_at(len - CHECKSUM_SIZE);
+        if checksum(payload) != check {
+            return Err(WifError::InvalidChecksum);
+        }
+
+        let network = match payload[0] {
+            MAINNET_PREFIX => Network::Mainnet,
+            TESTNET_PREFIX => Network::Testnet,
+            prefix => return Err(WifError::UnknownNetwork(prefix)),
+        };
+        let compressed = match payload.get(33) {
+            None => false,
+            Some(&COMPRESSED_FLAG) => true,
+            Some(&flag) => return Err(WifError::InvalidCompressionFlag(flag)),
+        };
+        let mut bytes = [0u8; constants::SECRET_KEY_SIZE];
+        bytes.copy_from_slice(&payload[1..33]);
+        let sk = SecretKey::from_secret_bytes(bytes).map_err(|_| WifError::InvalidSecretKey)?;
+        Ok((sk, network, compressed))
+    }
+}
+
+/// Computes the Base58Check checksum, the first four bytes of the double SHA-256 of `data`.
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let hash = sha256::hash(&sha256::hash(data));
+    let mut ret = [0u8; CHECKSUM_SIZE];
+    ret.copy_from_slice(&hash[..CHECKSUM_SIZE]);
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let sk = SecretKey::from_secret_bytes(hex!(
+            "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d"
+        ))
+        .unwrap();
+        let vectors = [
+            ("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ", Network::Mainnet, false),
+            ("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617", Network::Mainnet, true),
+            ("91gGn1HgSap6CbU12F6z3pJri26xzp7Ay1VW6NHCoEayNXwRpu2", Network::Testnet, false),
+            ("cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx", Network::Testnet, true),
+        ];
+        for (wif, network, compressed) in vectors {
+            assert_eq!(SecretKey::from_wif(wif), Ok((sk, network, compressed)));
+            #[cfg(feature = "alloc")]
+            assert_eq!(sk.to_wif(network, compressed), wif);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        // The last character changed.
+        assert_eq!(
+            SecretKey::from_wif("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98618"),
+            Err(WifError::InvalidChecksum)
+        );
+        assert_eq!(
+            SecretKey::from_wif("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP9861O"),
+            Err(WifError::InvalidBase58)
+        );
+        assert_eq!(SecretKey::from_wif(""), Err(WifError::InvalidLength));
+        assert_eq!(
+            SecretKey::from_wif("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617KwdM"),
+            Err(WifError::InvalidLength)
+        );
+        // A Base58Check address has a valid checksum but the wrong length.
+        assert_eq!(
+            SecretKey::from_wif("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM"),
+            Err(WifError::InvalidLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn invalid_contents() {
+        let encode = |payload: &[u8]| {
+            let mut data = [0u8; MAX_WIF_SIZE];
+            data[..payload.len()].copy_from_slice(payload);
+            data[payload.len()..payload.len() + 4].copy_from_slice(&checksum(payload));
+            let mut buf = [0u8; 2 * MAX_WIF_SIZE];
+            base58::encode(&data[..payload.len() + 4], &mut buf).unwrap().to_string()
+        };
+
+        let mut payload = [0u8; 34];
+        payload[0] = MAINNET_PREFIX;
+        payload[33] = COMPRESSED_FLAG;
+        assert_eq!(SecretKey::from_wif(&encode(&payload)), Err(WifError::InvalidSecretKey));
+        payload[1..33].copy_from_slice(&constants::CURVE_ORDER);
+        assert_eq!(SecretKey::from_wif(&encode(&payload)), Err(WifError::InvalidSecretKey));
+
+        payload[1..33].copy_from_slice(&constants::ONE);
+        payload[0] = 0x00;
+        assert_eq!(SecretKey::from_wif(&encode(&payload)), Err(WifError::UnknownNetwork(0x00)));
+        payload[0] = TESTNET_PREFIX;
+        payload[33] = 0x02;
+        assert_eq!(
+            SecretKey::from_wif(&encode(&payload)),
+            Err(WifError::InvalidCompressionFlag(0x02))
+        );
+    }
+}
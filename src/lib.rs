@@ -168,16 +168,30 @@ pub extern crate serde;
 mod macros;
 #[macro_use]
 mod secret;
+mod base58;
+#[cfg(any(feature = "alloc", feature = "recovery", feature = "serde"))]
+mod base64;
+#[cfg(feature = "nostr")]
+mod bech32;
+#[cfg(feature = "cose")]
+mod cbor;
+#[cfg(feature = "nostr")]
+mod chacha20;
 mod context;
 mod key;
 #[cfg(feature = "serde")]
 mod serde_util;
 
 pub mod constants;
+#[cfg(feature = "cose")]
+pub mod cose;
 pub mod ecdh;
 pub mod ecdsa;
 pub mod ellswift;
+pub mod hashes;
 pub mod musig;
+#[cfg(feature = "nostr")]
+pub mod nostr;
 pub mod scalar;
 pub mod schnorr;
 
@@ -195,6 +209,8 @@ pub use secp256k1_sys as ffi;
 pub use crate::context::global::{self, SECP256K1};
 #[cfg(feature = "alloc")]
 pub use crate::context::{All, SignOnly, VerifyOnly};
+#[cfg(feature = "serde")]
+pub use crate::key::Jwk;
 #[doc(inline)]
 pub use crate::{
     context::{
@@ -203,7 +219,8 @@ pub use crate::{
         VerifyOnlyPreallocated,
     },
     key::{
-        sort_pubkeys, InvalidParityValue, Keypair, Parity, PublicKey, SecretKey, XOnlyPublicKey,
+        sort_pubkeys, wif, InvalidParityValue, KeyDecodeError, Keypair, Parity, PublicKey,
+        SecretKey, XOnlyPublicKey,
     },
     scalar::Scalar,
 };
@@ -5,15 +5,27 @@
 //! The documentation in this module is for reference and may not be sufficient
 //! for advanced use-cases. A full description of the C API usage along with security considerations
 //! can be found in [C-musig.md](secp256k1-sys/depend/secp256k1/src/modules/musig/musig.md).
+//!
+//! Adaptor signatures are not supported. The vendored libsecp256k1 has no adaptor argument to
+//! `secp256k1_musig_nonce_process`, nor the `adapt` and `extract_adaptor` functions of the
+//! secp256k1-zkp fork, and [`Session`] is opaque, so it cannot be adjusted from Rust.
 use core;
 use core::fmt;
 use core::mem::MaybeUninit;
 #[cfg(feature = "std")]
 use std;
 
+#[cfg(feature = "alloc")]
+use crate::alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use crate::alloc::vec::Vec;
 use crate::ffi::{self, CPtr};
+use crate::hashes::hmac::Hmac;
+use crate::hashes::sha512;
 #[cfg(doc)]
 use crate::key;
+#[cfg(feature = "alloc")]
+use crate::Parity;
 use crate::{
     from_hex, schnorr, Error, Keypair, PublicKey, Scalar, Secp256k1, SecretKey, XOnlyPublicKey,
 };
@@ -31,6 +43,14 @@ pub const PUBNONCE_SERIALIZED_SIZE: usize = 66;
 /// aggregated into the final signature.
 pub const PART_SIG_SERIALIZED_SIZE: usize = 32;
 
+/// Chain code of the synthetic xpub for an aggregate public key, as specified by BIP-328.
+///
+/// This is the SHA-256 hash of the string "MuSig2MuSig2MuSig2".
+pub const BIP328_CHAIN_CODE: [u8; 32] = [
+    0x86, 0x80, 0x87, 0xca, 0x02, 0xa6, 0xf9, 0x74, 0xc4, 0x59, 0x89, 0x24, 0xc3, 0x6b, 0x57, 0x76,
+    0x2d, 0x32, 0xcb, 0x45, 0x71, 0x71, 0x67, 0xe3, 0x00, 0x62, 0x2c, 0x71, 0x67, 0xe3, 0x89, 0x65,
+];
+
 /// Musig parsing errors
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub enum ParseError {
@@ -106,6 +126,8 @@ impl SessionSecretRand {
 pub struct KeyAggCache {
     data: ffi::MusigKeyAggCache,
     aggregated_xonly_public_key: XOnlyPublicKey,
+    // Whether a tweak has been applied, after which BIP-328 derivation is no longer possible.
+    tweaked: bool,
 }
 
 impl CPtr for KeyAggCache {
@@ -129,6 +151,35 @@ impl fmt::Display for InvalidTweakErr {
     }
 }
 
+/// Error returned by [`KeyAggCache::derive_bip32`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum DeriveError {
+    /// The derivation path contains a hardened child number, which cannot be derived
+    /// from a public key.
+    HardenedIndex(u32),
+    /// The child number yields an invalid tweak or the point at infinity; BIP-32 says to
+    /// proceed with the next child number instead.
+    InvalidChild(u32),
+    /// The [`KeyAggCache`] has already been tweaked, so it no longer holds the aggregate key
+    /// that BIP-328 derives from.
+    TweakedCache,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeriveError {}
+
+impl fmt::Display for DeriveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            DeriveError::HardenedIndex(i) =>
+                write!(f, "cannot derive hardened child {} from an aggregate public key", i),
+            DeriveError::InvalidChild(i) => write!(f, "child number {} is invalid", i),
+            DeriveError::TweakedCache =>
+                f.write_str("cannot derive from a tweaked aggregate public key"),
+        }
+    }
+}
+
 /// Low level API for starting a signing session by generating a nonce.
 ///
 /// Use [`KeyAggCache::nonce_gen`] whenever
@@ -436,11 +487,49 @@ impl KeyAggCache {
                 // secp256k1_musig_pubkey_agg overwrites the cache and the key so this is sound.
                 let key_agg_cache = key_agg_cache.assume_init();
                 let agg_pk = XOnlyPublicKey::from(agg_pk.assume_init());
-                KeyAggCache { data: key_agg_cache, aggregated_xonly_public_key: agg_pk }
+                KeyAggCache {
+                    data: key_agg_cache,
+                    aggregated_xonly_public_key: agg_pk,
+                    tweaked: false,
+                }
             }
         }
     }
 
+    /// Creates a new [`KeyAggCache`] from a list of x-only public keys.
+    ///
+    /// Each x-only key is lifted to the point with even y-coordinate, as in BIP-340, before
+    /// being aggregated with [`KeyAggCache::new`]. A participant whose key pair has an odd
+    /// y-coordinate must therefore sign with the negation of its secret key, so that it
+    /// matches the lifted public key; see [`Participants::signing_keypair`].
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")]
+    /// # #[cfg(feature = "rand")] {
+    /// # use secp256k1::Keypair;
+    /// # use secp256k1::musig::KeyAggCache;
+    /// # let (xonly1, _) = Keypair::new(&mut rand::rng()).x_only_public_key();
+    /// # let (xonly2, _) = Keypair::new(&mut rand::rng()).x_only_public_key();
+    /// let key_agg_cache = KeyAggCache::from_xonly(&[&xonly1, &xonly2]);
+    /// let _agg_pk = key_agg_cache.agg_pk();
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an empty slice of pubkeys is provided.
+    #[cfg(feature = "alloc")]
+    pub fn from_xonly(pubkeys: &[&XOnlyPublicKey]) -> Self {
+        let pubkeys: Vec<PublicKey> = pubkeys
+            .iter()
+            .map(|pk| PublicKey::from_x_only_public_key(**pk, Parity::Even))
+            .collect();
+        let pubkeys_ref: Vec<&PublicKey> = pubkeys.iter().collect();
+        KeyAggCache::new(&pubkeys_ref)
+    }
+
     /// Obtains the aggregate public key for this [`KeyAggCache`]
     pub fn agg_pk(&self) -> XOnlyPublicKey { self.aggregated_xonly_public_key }
 
@@ -522,6 +611,7 @@ impl KeyAggCache {
                 Err(InvalidTweakErr)
             } else {
                 self.aggregated_xonly_public_key = out.x_only_public_key().0;
+                self.tweaked = true;
                 Ok(out)
             }
         }
@@ -582,11 +672,82 @@ impl KeyAggCache {
                 Err(InvalidTweakErr)
             } else {
                 self.aggregated_xonly_public_key = out.x_only_public_key().0;
+                self.tweaked = true;
                 Ok(out)
             }
         }
     }
 
+    /// Derives a child of the aggregate public key along an unhardened BIP-32 path, as
+    /// specified by BIP-328.
+    ///
+    /// The aggregate public key is treated as an extended public key with the chain code
+    /// [`BIP328_CHAIN_CODE`] and the derivation tweaks are applied to this [`KeyAggCache`]
+    /// with [`KeyAggCache::pubkey_ec_tweak_add`], so it can be used to sign for the derived key.
+    /// BIP-328 derivation starts from the untweaked aggregate key, so the cache must not have been
+    /// tweaked before, including by an earlier derivation along a non-empty path.
+    ///
+    /// # Returns
+    ///
+    /// The derived public key along with its chain code.
+    ///
+    /// # Errors
+    ///
+    /// If the cache has already been tweaked, if the path contains a hardened child number, or if
+    /// a child number yields an invalid key (with negligible probability). In all cases the cache
+    /// is left unmodified.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # #[cfg(not(secp256k1_fuzz))]
+    /// # #[cfg(feature = "std")]
+    /// # #[cfg(feature = "rand")] {
+    /// # use secp256k1::{SecretKey, PublicKey};
+    /// # use secp256k1::musig::KeyAggCache;
+    /// # let sk1 = SecretKey::new(&mut rand::rng());
+    /// # let pub_key1 = PublicKey::from_secret_key(&sk1);
+    /// # let sk2 = SecretKey::new(&mut rand::rng());
+    /// # let pub_key2 = PublicKey::from_secret_key(&sk2);
+    /// #
+    /// let mut key_agg_cache = KeyAggCache::new(&[&pub_key1, &pub_key2]);
+    ///
+    /// // Derive the key for the descriptor `musig(A,B)/0/7`.
+    /// let (child_pk, _chain_code) = key_agg_cache.derive_bip32(&[0, 7]).unwrap();
+    /// assert_eq!(key_agg_cache.agg_pk(), child_pk.x_only_public_key().0);
+    /// # }
+    /// ```
+    pub fn derive_bip32(&mut self, path: &[u32]) -> Result<(PublicKey, [u8; 32]), DeriveError> {
+        if self.tweaked {
+            return Err(DeriveError::TweakedCache);
+        }
+
+        let mut cache = *self;
+        let mut pk = cache.agg_pk_full();
+        let mut chain_code = BIP328_CHAIN_CODE;
+
+        for &index in path {
+            if index & (1 << 31) != 0 {
+                return Err(DeriveError::HardenedIndex(index));
+            }
+
+            let mut engine = Hmac::<sha512::HashEngine>::new(&chain_code);
+            engine.input(&pk.serialize());
+            engine.input(&index.to_be_bytes());
+            let hmac = engine.finalize();
+
+            let mut tweak = [0u8; 32];
+            tweak.copy_from_slice(&hmac[..32]);
+            let tweak =
+                Scalar::from_be_bytes(tweak).map_err(|_| DeriveError::InvalidChild(index))?;
+            pk = cache.pubkey_ec_tweak_add(&tweak).map_err(|_| DeriveError::InvalidChild(index))?;
+            chain_code.copy_from_slice(&hmac[32..]);
+        }
+
+        *self = cache;
+        Ok((pk, chain_code))
+    }
+
     /// Starts a signing session by generating a nonce
     ///
     /// This function outputs a secret nonce that will be required for signing and a
@@ -660,6 +821,126 @@ impl KeyAggCache {
     pub fn as_mut_ptr(&mut self) -> *mut ffi::MusigKeyAggCache { &mut self.data }
 }
 
+/// A sorted and deduplicated set of MuSig2 participants.
+///
+/// Key aggregation depends on the order of the public keys and on repeated keys, so the
+/// participants of a session must agree on the exact list being aggregated. This type
+/// fixes it to the canonical form: keys sorted with [`key::sort_pubkeys`] and with
+/// duplicates removed.
+///
+/// Example:
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # #[cfg(feature = "rand")] {
+/// # use secp256k1::{SecretKey, PublicKey};
+/// # use secp256k1::musig::Participants;
+/// # let pub_key1 = PublicKey::from_secret_key(&SecretKey::new(&mut rand::rng()));
+/// # let pub_key2 = PublicKey::from_secret_key(&SecretKey::new(&mut rand::rng()));
+/// let participants = Participants::new(&[&pub_key2, &pub_key1, &pub_key2]);
+/// assert_eq!(participants.len(), 2);
+///
+/// let my_index = participants.index_of(&pub_key1).expect("we are a participant");
+/// let _key_agg_cache = participants.key_agg_cache();
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Participants {
+    pubkeys: Vec<PublicKey>,
+}
+
+#[cfg(feature = "alloc")]
+impl Participants {
+    /// Creates the participant set from a list of public keys, sorting them and removing
+    /// duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an empty slice of pubkeys is provided.
+    pub fn new(pubkeys: &[&PublicKey]) -> Self {
+        if pubkeys.is_empty() {
+            panic!("Cannot aggregate an empty slice of pubkeys");
+        }
+
+        let mut sorted: Vec<&PublicKey> = pubkeys.to_vec();
+        crate::sort_pubkeys(&mut sorted);
+        sorted.dedup();
+        Participants { pubkeys: sorted.into_iter().copied().collect() }
+    }
+
+    /// Creates the participant set from a list of x-only public keys, sorting them and removing
+    /// duplicates.
+    ///
+    /// Each x-only key is lifted to the point with even y-coordinate, like in
+    /// [`KeyAggCache::from_xonly`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if an empty slice of pubkeys is provided.
+    pub fn from_xonly(pubkeys: &[&XOnlyPublicKey]) -> Self {
+        let pubkeys: Vec<PublicKey> = pubkeys
+            .iter()
+            .map(|pk| PublicKey::from_x_only_public_key(**pk, Parity::Even))
+            .collect();
+        let pubkeys_ref: Vec<&PublicKey> = pubkeys.iter().collect();
+        Participants::new(&pubkeys_ref)
+    }
+
+    /// Returns the number of distinct participants.
+    pub fn len(&self) -> usize { self.pubkeys.len() }
+
+    /// Returns `true` if there are no participants. This is never the case.
+    pub fn is_empty(&self) -> bool { self.pubkeys.is_empty() }
+
+    /// Returns the participants' public keys in aggregation order.
+    pub fn pubkeys(&self) -> &[PublicKey] { &self.pubkeys }
+
+    /// Returns the position of `pubkey` in aggregation order, or `None` if it is not a participant.
+    pub fn index_of(&self, pubkey: &PublicKey) -> Option<usize> {
+        self.pubkeys.iter().position(|pk| pk == pubkey)
+    }
+
+    /// Returns the index of the "second unique key" of BIP-327, if any.
+    ///
+    /// This is the first key that differs from the first key in aggregation order. Its key
+    /// aggregation coefficient is fixed to 1, which saves a scalar multiplication during
+    /// aggregation and signing. As the set is deduplicated this is `Some(1)` whenever there is
+    /// more than one participant.
+    pub fn second_unique_key_index(&self) -> Option<usize> {
+        let first = self.pubkeys.first()?;
+        self.pubkeys.iter().position(|pk| pk != first)
+    }
+
+    /// Returns whether `pubkey` is the "second unique key" of BIP-327.
+    pub fn is_second_unique_key(&self, pubkey: &PublicKey) -> bool {
+        match self.second_unique_key_index() {
+            Some(i) => self.pubkeys[i] == *pubkey,
+            None => false,
+        }
+    }
+
+    /// Returns the key pair to sign with for the participant owning `keypair`.
+    ///
+    /// Participants created from x-only keys are aggregated with the even-y lift of their
+    /// key, so a key pair with an odd y-coordinate has to be negated before signing. Returns
+    /// `None` if neither `keypair` nor its negation is a participant.
+    pub fn signing_keypair(&self, keypair: &Keypair) -> Option<Keypair> {
+        let pubkey = keypair.public_key();
+        if self.index_of(&pubkey).is_some() {
+            return Some(*keypair);
+        }
+        let negated = Keypair::from_secret_key(&keypair.secret_key().negate());
+        self.index_of(&negated.public_key()).map(|_| negated)
+    }
+
+    /// Creates the [`KeyAggCache`] for this set of participants.
+    pub fn key_agg_cache(&self) -> KeyAggCache {
+        let pubkeys_ref: Vec<&PublicKey> = self.pubkeys.iter().collect();
+        KeyAggCache::new(&pubkeys_ref)
+    }
+}
+
 /// Musig Secret Nonce.
 ///
 /// A signer who is online throughout the whole process and can keep this structure
@@ -1391,6 +1672,241 @@ impl Session {
     pub fn as_mut_ptr(&mut self) -> *mut ffi::MusigSession { &mut self.0 }
 }
 
+/// Errors returned by [`Coordinator`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum CoordinatorError {
+    /// The same public key was listed more than once as a participant.
+    DuplicateParticipant(PublicKey),
+    /// The public key is not one of the participants of the session.
+    UnknownParticipant(PublicKey),
+    /// A public nonce was already received from this participant.
+    DuplicateNonce(PublicKey),
+    /// A partial signature was already received from this participant.
+    DuplicatePartialSignature(PublicKey),
+    /// A partial signature was received before the nonces of all participants.
+    NoncesIncomplete,
+    /// The partial signature of this participant does not verify.
+    InvalidPartialSignature(PublicKey),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CoordinatorError {}
+
+impl fmt::Display for CoordinatorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            CoordinatorError::DuplicateParticipant(pk) =>
+                write!(f, "participant {} is listed more than once", pk),
+            CoordinatorError::UnknownParticipant(pk) => write!(f, "unknown participant {}", pk),
+            CoordinatorError::DuplicateNonce(pk) =>
+                write!(f, "already received a nonce from participant {}", pk),
+            CoordinatorError::DuplicatePartialSignature(pk) =>
+                write!(f, "already received a partial signature from participant {}", pk),
+            CoordinatorError::NoncesIncomplete =>
+                write!(f, "partial signature received before all nonces"),
+            CoordinatorError::InvalidPartialSignature(pk) =>
+                write!(f, "invalid partial signature from participant {}", pk),
+        }
+    }
+}
+
+/// Aggregates nonces and partial signatures for a MuSig2 signing session.
+///
+/// The coordinator only handles public material, so it can run on an untrusted relay between
+/// the signers. It collects a [`PublicNonce`] from every participant and produces the
+/// [`AggregatedNonce`] once all of them have arrived. Then it verifies each incoming
+/// [`PartialSignature`] with [`Session::partial_verify`] and, once all of them have arrived,
+/// produces the final signature.
+///
+/// A misbehaving participant is identified through the returned [`CoordinatorError`]. The
+/// coordinator does not track time; dealing with participants that never respond is left to
+/// the caller, e.g. by inspecting [`Coordinator::missing_nonces`] after a timeout.
+///
+/// Example:
+///
+/// ```rust
+/// # #[cfg(not(secp256k1_fuzz))]
+/// # #[cfg(feature = "std")]
+/// # #[cfg(feature = "rand")] {
+/// # use secp256k1::{Keypair, PublicKey, SecretKey};
+/// # use secp256k1::musig::{Coordinator, KeyAggCache, Session, SessionSecretRand};
+/// # let sk1 = SecretKey::new(&mut rand::rng());
+/// # let pub_key1 = PublicKey::from_secret_key(&sk1);
+/// # let sk2 = SecretKey::new(&mut rand::rng());
+/// # let pub_key2 = PublicKey::from_secret_key(&sk2);
+/// let key_agg_cache = KeyAggCache::new(&[&pub_key1, &pub_key2]);
+/// let msg = b"Public message we want to sign!!";
+/// let mut coordinator = Coordinator::new(&[&pub_key1, &pub_key2], key_agg_cache, msg).unwrap();
+///
+/// // Signers send their public nonces.
+/// let (sec_nonce1, pub_nonce1) =
+///     key_agg_cache.nonce_gen(SessionSecretRand::from_rng(&mut rand::rng()), pub_key1, msg, None);
+/// let (sec_nonce2, pub_nonce2) =
+///     key_agg_cache.nonce_gen(SessionSecretRand::from_rng(&mut rand::rng()), pub_key2, msg, None);
+/// assert!(coordinator.add_nonce(pub_key1, pub_nonce1).unwrap().is_none());
+/// let agg_nonce = coordinator.add_nonce(pub_key2, pub_nonce2).unwrap().unwrap();
+///
+/// // The aggregated nonce is sent back to the signers, who send their partial signatures.
+/// let session = Session::new(&key_agg_cache, agg_nonce, msg);
+/// let partial_sig1 = session.partial_sign(sec_nonce1, &Keypair::from_secret_key(&sk1), &key_agg_cache);
+/// let partial_sig2 = session.partial_sign(sec_nonce2, &Keypair::from_secret_key(&sk2), &key_agg_cache);
+/// assert!(coordinator.add_partial_signature(pub_key1, partial_sig1).unwrap().is_none());
+/// let sig = coordinator.add_partial_signature(pub_key2, partial_sig2).unwrap().unwrap();
+///
+/// secp256k1::schnorr::verify(&sig, msg, &key_agg_cache.agg_pk()).unwrap();
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coordinator {
+    key_agg_cache: KeyAggCache,
+    msg: [u8; 32],
+    participants: Vec<PublicKey>,
+    nonces: BTreeMap<PublicKey, PublicNonce>,
+    session: Option<(AggregatedNonce, Session)>,
+    partial_sigs: BTreeMap<PublicKey, PartialSignature>,
+}
+
+#[cfg(feature = "alloc")]
+impl Coordinator {
+    /// Creates a new coordinator for signing `msg` with `key_agg_cache`.
+    ///
+    /// `participants` are the public keys of the signers. The caller is responsible for passing
+    /// the [`KeyAggCache`] that was created from exactly these keys, including any tweaks applied
+    /// to it.
+    ///
+    /// # Errors
+    ///
+    /// If a public key is listed more than once. Such sessions require several nonces and partial
+    /// signatures from the same key, which can't be told apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an empty slice of participants is provided.
+    pub fn new(
+        participants: &[&PublicKey],
+        key_agg_cache: KeyAggCache,
+        msg: &[u8; 32],
+    ) -> Result<Self, CoordinatorError> {
+        if participants.is_empty() {
+            panic!("Cannot coordinate a session without participants");
+        }
+
+        let mut keys = Vec::with_capacity(participants.len());
+        for pk in participants {
+            if keys.contains(*pk) {
+                return Err(CoordinatorError::DuplicateParticipant(**pk));
+            }
+            keys.push(**pk);
+        }
+
+        Ok(Coordinator {
+            key_agg_cache,
+            msg: *msg,
+            participants: keys,
+            nonces: BTreeMap::new(),
+            session: None,
+            partial_sigs: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the public keys of the participants.
+    pub fn participants(&self) -> &[PublicKey] { &self.participants }
+
+    /// Adds the public nonce of `participant`.
+    ///
+    /// # Returns
+    ///
+    /// The [`AggregatedNonce`] once the nonces of all participants have been received,
+    /// `None` otherwise.
+    pub fn add_nonce(
+        &mut self,
+        participant: PublicKey,
+        nonce: PublicNonce,
+    ) -> Result<Option<AggregatedNonce>, CoordinatorError> {
+        if !self.participants.contains(&participant) {
+            return Err(CoordinatorError::UnknownParticipant(participant));
+        }
+        if self.nonces.contains_key(&participant) {
+            return Err(CoordinatorError::DuplicateNonce(participant));
+        }
+        self.nonces.insert(participant, nonce);
+
+        if self.nonces.len() < self.participants.len() {
+            return Ok(None);
+        }
+
+        let nonces: Vec<&PublicNonce> = self.nonces.values().collect();
+        let agg_nonce = AggregatedNonce::new(&nonces);
+        let session = Session::new(&self.key_agg_cache, agg_nonce, &self.msg);
+        self.session = Some((agg_nonce, session));
+        Ok(Some(agg_nonce))
+    }
+
+    /// Returns the participants whose public nonce has not been received yet.
+    pub fn missing_nonces(&self) -> impl Iterator<Item = &PublicKey> {
+        self.participants.iter().filter(move |pk| !self.nonces.contains_key(pk))
+    }
+
+    /// Returns the aggregated nonce, if the nonces of all participants have been received.
+    pub fn aggregated_nonce(&self) -> Option<AggregatedNonce> {
+        self.session.as_ref().map(|(agg_nonce, _)| *agg_nonce)
+    }
+
+    /// Returns the signing session, if the nonces of all participants have been received.
+    pub fn session(&self) -> Option<&Session> { self.session.as_ref().map(|(_, session)| session) }
+
+    /// Verifies and adds the partial signature of `participant`.
+    ///
+    /// # Returns
+    ///
+    /// The final signature once valid partial signatures of all participants have been received,
+    /// `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If not all nonces have been received yet, or if the partial signature does not verify.
+    /// An invalid partial signature is not stored, so the participant may retry.
+    pub fn add_partial_signature(
+        &mut self,
+        participant: PublicKey,
+        partial_sig: PartialSignature,
+    ) -> Result<Option<schnorr::Signature>, CoordinatorError> {
+        if !self.participants.contains(&participant) {
+            return Err(CoordinatorError::UnknownParticipant(participant));
+        }
+        let session = match self.session {
+            Some((_, ref session)) => session,
+            None => return Err(CoordinatorError::NoncesIncomplete),
+        };
+        if self.partial_sigs.contains_key(&participant) {
+            return Err(CoordinatorError::DuplicatePartialSignature(participant));
+        }
+        if !session.partial_verify(
+            &self.key_agg_cache,
+            &partial_sig,
+            &self.nonces[&participant],
+            participant,
+        ) {
+            return Err(CoordinatorError::InvalidPartialSignature(participant));
+        }
+        self.partial_sigs.insert(participant, partial_sig);
+
+        if self.partial_sigs.len() < self.participants.len() {
+            return Ok(None);
+        }
+
+        // The aggregate of valid partial signatures is a valid signature.
+        let partial_sigs: Vec<&PartialSignature> = self.partial_sigs.values().collect();
+        Ok(Some(session.partial_sig_agg(&partial_sigs).assume_valid()))
+    }
+
+    /// Returns the participants whose partial signature has not been received yet.
+    pub fn missing_partial_signatures(&self) -> impl Iterator<Item = &PublicKey> {
+        self.participants.iter().filter(move |pk| !self.partial_sigs.contains_key(pk))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1473,11 +1989,219 @@ mod tests {
         assert_eq!(key_agg_cache.agg_pk(), tweaked_agg_pk.x_only_public_key().0);
     }
 
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    #[cfg(feature = "std")]
+    fn key_agg_cache_derive_bip32() {
+        use core::str::FromStr;
+
+        // Participants of the first BIP-328 test vector.
+        let pubkey1 = PublicKey::from_str(
+            "03935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9",
+        )
+        .unwrap();
+        let pubkey2 = PublicKey::from_str(
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        )
+        .unwrap();
+
+        let mut key_agg_cache = KeyAggCache::new(&[&pubkey1, &pubkey2]);
+        assert_eq!(
+            key_agg_cache.agg_pk_full().to_string(),
+            "0354240c76b8f2999143301a99c7f721ee57eee0bce401df3afeaa9ae218c70f23",
+        );
+
+        // An empty path leaves the aggregate key untouched. Its extended public key is the one
+        // given for these participants in the BIP-328 test vectors.
+        let mut copy = key_agg_cache;
+        let (pk, chain_code) = copy.derive_bip32(&[]).unwrap();
+        assert_eq!(pk, key_agg_cache.agg_pk_full());
+        assert_eq!(chain_code, BIP328_CHAIN_CODE);
+        let mut xpub = [0u8; 82];
+        let xpub_str = "xpub661MyMwAqRbcFt6tk3uaczE1y6EvM1TqXvawXcYmFEWijEM4PDBnuCXwwXEKGEouzXE6QLLRxjatMcLLzJ5LV5Nib1BN7vJg6yp45yHHRbm";
+        assert_eq!(crate::base58::decode(xpub_str, &mut xpub), Ok(82));
+        assert_eq!(xpub[13..45], chain_code);
+        assert_eq!(xpub[45..78], pk.serialize());
+
+        // Hardened derivation fails without modifying the cache.
+        let original = key_agg_cache;
+        assert_eq!(
+            key_agg_cache.derive_bip32(&[0, 0x8000_0001]),
+            Err(DeriveError::HardenedIndex(0x8000_0001))
+        );
+        assert_eq!(key_agg_cache, original);
+
+        // Not a BIP-328 test vector: this result was cross-checked against an independent Python
+        // implementation of BIP-32 public derivation from the aggregate key and chain code above.
+        let (pk, chain_code) = key_agg_cache.derive_bip32(&[0, 1, 42]).unwrap();
+        assert_eq!(
+            pk.to_string(),
+            "02de369681089a66bc9c1bef9b2707d1ddf745f398f2219d408f1a9d6fcfd1670f",
+        );
+        assert_eq!(
+            chain_code,
+            hex_lit::hex!("269a8a20cb81e0ff4d423f5a591fe4f331e28f355fd4a894db0cb727ddaffb15"),
+        );
+        assert_eq!(key_agg_cache.agg_pk_full(), pk);
+        assert_eq!(key_agg_cache.agg_pk(), pk.x_only_public_key().0);
+
+        // The derived cache is tweaked, as is one tweaked directly.
+        let original = key_agg_cache;
+        assert_eq!(key_agg_cache.derive_bip32(&[0]), Err(DeriveError::TweakedCache));
+        assert_eq!(key_agg_cache, original);
+        let mut tweaked = KeyAggCache::new(&[&pubkey1, &pubkey2]);
+        tweaked.pubkey_xonly_tweak_add(&Scalar::ONE).unwrap();
+        assert_eq!(tweaked.derive_bip32(&[]), Err(DeriveError::TweakedCache));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    #[cfg(feature = "std")]
+    #[cfg(feature = "rand")]
+    fn derive_bip32_signing() {
+        let mut rng = rand::rng();
+
+        let (seckey1, pubkey1) = crate::test_random_keypair();
+        let (seckey2, pubkey2) = crate::test_random_keypair();
+
+        let mut key_agg_cache = KeyAggCache::new(&[&pubkey1, &pubkey2]);
+        let (child_pk, _) = key_agg_cache.derive_bip32(&[0, 5]).unwrap();
+
+        let msg: &[u8; 32] = b"This message is exactly 32 bytes";
+
+        let session_secrand1 = SessionSecretRand::from_rng(&mut rng);
+        let (sec_nonce1, pub_nonce1) =
+            key_agg_cache.nonce_gen(session_secrand1, pubkey1, msg, None);
+        let session_secrand2 = SessionSecretRand::from_rng(&mut rng);
+        let (sec_nonce2, pub_nonce2) =
+            key_agg_cache.nonce_gen(session_secrand2, pubkey2, msg, None);
+
+        let agg_nonce = AggregatedNonce::new(&[&pub_nonce1, &pub_nonce2]);
+        let session = Session::new(&key_agg_cache, agg_nonce, msg);
+
+        let partial_sign1 =
+            session.partial_sign(sec_nonce1, &Keypair::from_secret_key(&seckey1), &key_agg_cache);
+        let partial_sign2 =
+            session.partial_sign(sec_nonce2, &Keypair::from_secret_key(&seckey2), &key_agg_cache);
+
+        let aggregated_signature = session.partial_sig_agg(&[&partial_sign1, &partial_sign2]);
+        aggregated_signature.verify(&child_pk.x_only_public_key().0, msg).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    #[cfg(feature = "std")]
+    fn key_agg_cache_from_xonly() {
+        let (_seckey1, pubkey1) = crate::test_random_keypair();
+        let (_seckey2, pubkey2) = crate::test_random_keypair();
+        let (xonly1, _) = pubkey1.x_only_public_key();
+        let (xonly2, _) = pubkey2.x_only_public_key();
+
+        let even1 = PublicKey::from_x_only_public_key(xonly1, Parity::Even);
+        let even2 = PublicKey::from_x_only_public_key(xonly2, Parity::Even);
+
+        let key_agg_cache = KeyAggCache::from_xonly(&[&xonly1, &xonly2]);
+        assert_eq!(key_agg_cache, KeyAggCache::new(&[&even1, &even2]));
+        assert_eq!(key_agg_cache, KeyAggCache::from_xonly(&[&xonly1, &xonly2]));
+        assert_ne!(key_agg_cache, KeyAggCache::from_xonly(&[&xonly2, &xonly1]));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    #[cfg(feature = "std")]
+    fn participants() {
+        let (_seckey1, pubkey1) = crate::test_random_keypair();
+        let (_seckey2, pubkey2) = crate::test_random_keypair();
+        let (_seckey3, pubkey3) = crate::test_random_keypair();
+        let (_seckey4, pubkey4) = crate::test_random_keypair();
+
+        let mut sorted = [&pubkey1, &pubkey2, &pubkey3];
+        crate::sort_pubkeys(&mut sorted);
+
+        let participants = Participants::new(&[&pubkey3, &pubkey1, &pubkey2, &pubkey1, &pubkey3]);
+        assert_eq!(participants.len(), 3);
+        assert!(!participants.is_empty());
+        assert_eq!(participants.pubkeys(), [*sorted[0], *sorted[1], *sorted[2]]);
+        assert_eq!(participants, Participants::new(&[&pubkey2, &pubkey3, &pubkey1]));
+        assert_eq!(participants.key_agg_cache(), KeyAggCache::new(&sorted));
+
+        for (i, pk) in sorted.iter().enumerate() {
+            assert_eq!(participants.index_of(pk), Some(i));
+        }
+        assert_eq!(participants.index_of(&pubkey4), None);
+
+        assert_eq!(participants.second_unique_key_index(), Some(1));
+        assert!(participants.is_second_unique_key(sorted[1]));
+        assert!(!participants.is_second_unique_key(sorted[0]));
+        assert!(!participants.is_second_unique_key(&pubkey4));
+
+        let single = Participants::new(&[&pubkey1, &pubkey1]);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.second_unique_key_index(), None);
+        assert!(!single.is_second_unique_key(&pubkey1));
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    #[cfg(feature = "std")]
+    #[cfg(feature = "rand")]
+    fn participants_from_xonly_signing() {
+        let mut rng = rand::rng();
+
+        // Make sure at least one participant has an odd y-coordinate.
+        let mut keypair1 = Keypair::new(&mut rng);
+        if keypair1.x_only_public_key().1 == Parity::Even {
+            keypair1 = Keypair::from_secret_key(&keypair1.secret_key().negate());
+        }
+        let keypair2 = Keypair::new(&mut rng);
+        let (xonly1, _) = keypair1.x_only_public_key();
+        let (xonly2, _) = keypair2.x_only_public_key();
+
+        let participants = Participants::from_xonly(&[&xonly1, &xonly2]);
+        let key_agg_cache = participants.key_agg_cache();
+        assert_eq!(participants.index_of(&keypair1.public_key()), None);
+
+        let signer1 = participants.signing_keypair(&keypair1).unwrap();
+        let signer2 = participants.signing_keypair(&keypair2).unwrap();
+        assert_ne!(signer1, keypair1);
+        assert_eq!(signer1.x_only_public_key().0, xonly1);
+        assert!(participants.signing_keypair(&Keypair::new(&mut rng)).is_none());
+
+        let msg: &[u8; 32] = b"This message is exactly 32 bytes";
+
+        let session_secrand1 = SessionSecretRand::from_rng(&mut rng);
+        let (sec_nonce1, pub_nonce1) =
+            key_agg_cache.nonce_gen(session_secrand1, signer1.public_key(), msg, None);
+        let session_secrand2 = SessionSecretRand::from_rng(&mut rng);
+        let (sec_nonce2, pub_nonce2) =
+            key_agg_cache.nonce_gen(session_secrand2, signer2.public_key(), msg, None);
+
+        let agg_nonce = AggregatedNonce::new(&[&pub_nonce1, &pub_nonce2]);
+        let session = Session::new(&key_agg_cache, agg_nonce, msg);
+
+        let partial_sign1 = session.partial_sign(sec_nonce1, &signer1, &key_agg_cache);
+        let partial_sign2 = session.partial_sign(sec_nonce2, &signer2, &key_agg_cache);
+        assert!(session.partial_verify(
+            &key_agg_cache,
+            &partial_sign1,
+            &pub_nonce1,
+            signer1.public_key()
+        ));
+
+        let aggregated_signature = session.partial_sig_agg(&[&partial_sign1, &partial_sign2]);
+        aggregated_signature.verify(&key_agg_cache.agg_pk(), msg).unwrap();
+    }
+
     #[test]
     #[cfg(feature = "std")]
     #[should_panic(expected = "Cannot aggregate an empty slice of pubkeys")]
     fn key_agg_cache_empty_panic() { let _ = KeyAggCache::new(&[]); }
 
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "Cannot aggregate an empty slice of pubkeys")]
+    fn participants_empty_panic() { let _ = Participants::new(&[]); }
+
     #[test]
     #[cfg(feature = "std")]
     #[cfg(feature = "rand")]
@@ -1693,6 +2417,95 @@ mod tests {
         let _agg_sig = session.partial_sig_agg(&[]);
     }
 
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    #[cfg(feature = "std")]
+    #[cfg(feature = "rand")]
+    fn coordinator() {
+        let mut rng = rand::rng();
+
+        let (seckey1, pubkey1) = crate::test_random_keypair();
+        let (seckey2, pubkey2) = crate::test_random_keypair();
+        let (_seckey3, pubkey3) = crate::test_random_keypair();
+
+        let key_agg_cache = KeyAggCache::new(&[&pubkey1, &pubkey2]);
+        let msg: &[u8; 32] = b"This message is exactly 32 bytes";
+
+        assert_eq!(
+            Coordinator::new(&[&pubkey1, &pubkey2, &pubkey1], key_agg_cache, msg),
+            Err(CoordinatorError::DuplicateParticipant(pubkey1))
+        );
+        let mut coordinator = Coordinator::new(&[&pubkey1, &pubkey2], key_agg_cache, msg).unwrap();
+        assert_eq!(coordinator.participants(), [pubkey1, pubkey2]);
+
+        let (sec_nonce1, pub_nonce1) =
+            key_agg_cache.nonce_gen(SessionSecretRand::from_rng(&mut rng), pubkey1, msg, None);
+        let (sec_nonce2, pub_nonce2) =
+            key_agg_cache.nonce_gen(SessionSecretRand::from_rng(&mut rng), pubkey2, msg, None);
+        let (_, pub_nonce3) =
+            key_agg_cache.nonce_gen(SessionSecretRand::from_rng(&mut rng), pubkey3, msg, None);
+        let (other_sec_nonce1, other_pub_nonce1) =
+            key_agg_cache.nonce_gen(SessionSecretRand::from_rng(&mut rng), pubkey1, msg, None);
+
+        assert_eq!(
+            coordinator.add_nonce(pubkey3, pub_nonce3),
+            Err(CoordinatorError::UnknownParticipant(pubkey3))
+        );
+        assert_eq!(coordinator.add_nonce(pubkey1, pub_nonce1), Ok(None));
+        assert_eq!(
+            coordinator.add_nonce(pubkey1, pub_nonce3),
+            Err(CoordinatorError::DuplicateNonce(pubkey1))
+        );
+        assert_eq!(coordinator.missing_nonces().collect::<Vec<_>>(), [&pubkey2]);
+        assert_eq!(coordinator.aggregated_nonce(), None);
+
+        let other_session =
+            Session::new(&key_agg_cache, AggregatedNonce::new(&[&other_pub_nonce1]), msg);
+        let early_sig = other_session.partial_sign(
+            other_sec_nonce1,
+            &Keypair::from_secret_key(&seckey1),
+            &key_agg_cache,
+        );
+        assert_eq!(
+            coordinator.add_partial_signature(pubkey1, early_sig),
+            Err(CoordinatorError::NoncesIncomplete)
+        );
+
+        let agg_nonce = coordinator.add_nonce(pubkey2, pub_nonce2).unwrap().unwrap();
+        assert_eq!(agg_nonce, AggregatedNonce::new(&[&pub_nonce1, &pub_nonce2]));
+        assert_eq!(coordinator.aggregated_nonce(), Some(agg_nonce));
+        assert_eq!(coordinator.missing_nonces().count(), 0);
+
+        let session = Session::new(&key_agg_cache, agg_nonce, msg);
+        assert_eq!(coordinator.session(), Some(&session));
+
+        // A partial signature over a different session is rejected and can be retried.
+        assert_eq!(
+            coordinator.add_partial_signature(pubkey1, early_sig),
+            Err(CoordinatorError::InvalidPartialSignature(pubkey1))
+        );
+        assert_eq!(coordinator.missing_partial_signatures().count(), 2);
+
+        let partial_sig2 =
+            session.partial_sign(sec_nonce2, &Keypair::from_secret_key(&seckey2), &key_agg_cache);
+        assert_eq!(
+            coordinator.add_partial_signature(pubkey3, partial_sig2),
+            Err(CoordinatorError::UnknownParticipant(pubkey3))
+        );
+        assert_eq!(coordinator.add_partial_signature(pubkey2, partial_sig2), Ok(None));
+        assert_eq!(
+            coordinator.add_partial_signature(pubkey2, partial_sig2),
+            Err(CoordinatorError::DuplicatePartialSignature(pubkey2))
+        );
+        assert_eq!(coordinator.missing_partial_signatures().collect::<Vec<_>>(), [&pubkey1]);
+
+        let partial_sig1 =
+            session.partial_sign(sec_nonce1, &Keypair::from_secret_key(&seckey1), &key_agg_cache);
+        let sig = coordinator.add_partial_signature(pubkey1, partial_sig1).unwrap().unwrap();
+        schnorr::verify(&sig, msg, &key_agg_cache.agg_pk()).unwrap();
+        assert_eq!(coordinator.missing_partial_signatures().count(), 0);
+    }
+
     #[test]
     fn de_serialization() {
         const MUSIG_PUBLIC_NONCE_HEX: &str = "03f4a361abd3d50535be08421dbc73b0a8f595654ae3238afcaf2599f94e25204c036ba174214433e21f5cd0fcb14b038eb40b05b7e7c820dd21aa568fdb0a9de4d7";
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Nostr events, keys and encryption.
+//!
+//! Nostr is built entirely on BIP-340: events are identified by the SHA-256 hash of their
+//! serialization ([NIP-01]) and signed with Schnorr signatures, keys are shared as bech32 strings
+//! ([NIP-19], see [`nip19`]) and direct messages are encrypted with a key derived from x-only
+//! ECDH ([NIP-44], see [`nip44`]).
+//!
+//! [NIP-01]: https://github.com/nostr-protocol/nips/blob/master/01.md
+//! [NIP-19]: https://github.com/nostr-protocol/nips/blob/master/19.md
+//! [NIP-44]: https://github.com/nostr-protocol/nips/blob/master/44.md
+
+pub mod nip19;
+pub mod nip44;
+
+use core::fmt::{self, Write as _};
+
+use crate::alloc::string::String;
+use crate::alloc::vec::Vec;
+use crate::hashes::sha256;
+use crate::{schnorr, Keypair, XOnlyPublicKey};
+
+/// A signed Nostr event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    /// The event ID, see [`event_id`].
+    pub id: [u8; 32],
+    /// The public key of the author.
+    pub pubkey: XOnlyPublicKey,
+    /// The creation time, in seconds since the Unix epoch.
+    pub created_at: u64,
+    /// The kind of the event.
+    pub kind: u16,
+    /// The tags of the event, each a list of strings.
+    pub tags: Vec<Vec<String>>,
+    /// The content of the event.
+    pub content: String,
+    /// The signature of the author over the event ID.
+    pub sig: schnorr::Signature,
+}
+
+impl Event {
+    /// Creates and signs an event, using the thread-local random number generator for the
+    /// auxiliary randomness of the signature.
+    #[cfg(all(feature = "rand", feature = "std"))]
+    pub fn sign(
+        keypair: &Keypair,
+        created_at: u64,
+        kind: u16,
+        tags: Vec<Vec<String>>,
+        content: String,
+    ) -> Event {
+        let aux_rand = crate::random_32_bytes(&mut rand::rng());
+        Event::sign_with_aux_rand(keypair, created_at, kind, tags, content, &aux_rand)
+    }
+
+    /// Creates and signs an event, using the given auxiliary randomness for the signature.
+    pub fn sign_with_aux_rand(
+        keypair: &Keypair,
+        created_at: u64,
+        kind: u16,
+        tags: Vec<Vec<String>>,
+        content: String,
+        aux_rand: &[u8; 32],
+    ) -> Event {
+        let (pubkey, _) = keypair.x_only_public_key();
+        let id = event_id(&pubkey, created_at, kind, &tags, &content);
+        let sig = schnorr::sign_with_aux_rand(&id, keypair, aux_rand);
+        Event { id, pubkey, created_at, kind, tags, content, sig }
+    }
+
+    /// Computes the ID of the event from its contents.
+    pub fn compute_id(&self) -> [u8; 32] {
+        event_id(&self.pubkey, self.created_at, self.kind, &self.tags, &self.content)
+    }
+
+    /// Checks that the ID matches the contents and that the signature is valid.
+    pub fn verify(&self) -> Result<(), EventError> {
+        if self.compute_id() != self.id {
+            return Err(EventError::IncorrectId);
+        }
+        schnorr::verify(&self.sig, &self.id, &self.pubkey).map_err(|_| EventError::InvalidSignature)
+    }
+}
+
+/// Computes the ID of an event, the SHA-256 hash of the JSON array
+/// `[0, pubkey, created_at, kind, tags, content]` serialized as specified in NIP-01.
+pub fn event_id(
+    pubkey: &XOnlyPublicKey,
+    created_at: u64,
+    kind: u16,
+    tags: &[Vec<String>],
+    content: &str,
+) -> [u8; 32] {
+    let mut writer = HashWriter(sha256::HashEngine::default());
+    write!(writer, "[0,\"{}\",{},{},[", pubkey, created_at, kind).expect("hashing does not fail");
+    for (i, tag) in tags.iter().enumerate() {
+        writer.write_str(if i == 0 { "[" } else { ",[" }).expect("hashing does not fail");
+        for (j, value) in tag.iter().enumerate() {
+            if j > 0 {
+                writer.write_str(",").expect("hashing does not fail");
+            }
+            writer.write_json_string(value);
+        }
+        writer.write_str("]").expect("hashing does not fail");
+    }
+    writer.write_str("],").expect("hashing does not fail");
+    writer.write_json_string(content);
+    writer.write_str("]").expect("hashing does not fail");
+    writer.0.finalize()
+}
+
+/// Feeds formatted text into a SHA-256 engine.
+struct HashWriter(sha256::HashEngine);
+
+impl HashWriter {
+    /// Writes `s` as a JSON string, escaping only the characters NIP-01 requires.
+    fn write_json_string(&mut self, s: &str) {
+        self.0.input(b"\"");
+        let mut start = 0;
+        for (i, byte) in s.bytes().enumerate() {
+            let escaped: &[u8] = match byte {
+                b'"' => b"\\\"",
+                b'\\' => b"\\\\",
+                b'\n' => b"\\n",
+                b'\r' => b"\\r",
+                b'\t' => b"\\t",
+                0x08 => b"\\b",
+                0x0c => b"\\f",
+                _ => continue,
+            };
+            self.0.input(&s.as_bytes()[start..i]);
+            self.0.input(escaped);
+            start = i + 1;
+        }
+        self.0.input(&s.as_bytes()[start..]);
+        self.0.input(b"\"");
+    }
+}
+
+impl fmt::Write for HashWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.input(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Errors returned when verifying a Nostr event.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum EventError {
+    /// The event ID does not match the contents of the event.
+    IncorrectId,
+    /// The signature is not valid for the event ID and public key.
+    InvalidSignature,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EventError {}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            EventError::IncorrectId => f.write_str("event ID does not match the event"),
+            EventError::InvalidSignature => f.write_str("invalid event signature"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+    use crate::alloc::string::ToString;
+    use crate::alloc::vec;
+
+    fn tags() -> Vec<Vec<String>> {
+        vec![
+            vec![
+                "e".to_string(),
+                "5c83da77af1dec6d7289834998ad7aafbd9e2191396d75ec3cc27f5a77226f36".to_string(),
+            ],
+            vec!["t".to_string(), "nostr".to_string(), "".to_string()],
+        ]
+    }
+
+    #[test]
+    fn event_id_vector() {
+        // Computed with Python's `json.dumps(..., separators=(",", ":"), ensure_ascii=False)`.
+        let pubkey =
+            "7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e".parse().unwrap();
+        let content = "Hello \"nostr\"\n\ttab\\ \u{8}\u{c}\r ünïcödé 🍕";
+        assert_eq!(
+            event_id(&pubkey, 1700000000, 1, &tags(), content),
+            hex!("b28764dca839e035a25b4524dec1f75e962221c217fb0c97ccd7c2db7ed0c318")
+        );
+        assert_eq!(
+            event_id(&pubkey, 0, 0, &[], ""),
+            hex!("d809cf9dc1dd63bc14ea9b3ba940243cb931801dbddc92f5cc0f051662a16670")
+        );
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn sign_verify() {
+        let keypair = Keypair::from_secret_key(&crate::test_random_keypair().0);
+        let event = Event::sign_with_aux_rand(
+            &keypair,
+            1700000000,
+            1,
+            tags(),
+            "hello".to_string(),
+            &[0; 32],
+        );
+        assert_eq!(event.pubkey, keypair.x_only_public_key().0);
+        assert_eq!(event.verify(), Ok(()));
+
+        let mut changed = event.clone();
+        changed.content.push('!');
+        assert_eq!(changed.verify(), Err(EventError::IncorrectId));
+        changed.id = changed.compute_id();
+        assert_eq!(changed.verify(), Err(EventError::InvalidSignature));
+    }
+}
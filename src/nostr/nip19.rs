@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! NIP-19 bech32 encoding of keys and event IDs.
+//!
+//! Only the bare entities are supported: public keys (`npub`), secret keys (`nsec`) and event IDs
+//! (`note`), not the TLV-based shareable identifiers.
+
+use core::fmt;
+
+use crate::alloc::string::String;
+use crate::{bech32, SecretKey, XOnlyPublicKey};
+
+/// The human-readable part of an encoded public key.
+pub const NPUB_PREFIX: &str = "npub";
+/// The human-readable part of an encoded secret key.
+pub const NSEC_PREFIX: &str = "nsec";
+/// The human-readable part of an encoded event ID.
+pub const NOTE_PREFIX: &str = "note";
+
+/// Encodes an x-only public key as an `npub1...` string.
+pub fn encode_npub(pubkey: &XOnlyPublicKey) -> String {
+    bech32::encode(NPUB_PREFIX, &pubkey.serialize())
+}
+
+/// Decodes an `npub1...` string into an x-only public key.
+pub fn decode_npub(s: &str) -> Result<XOnlyPublicKey, Nip19Error> {
+    let bytes = decode_32(NPUB_PREFIX, s)?;
+    XOnlyPublicKey::from_byte_array(bytes).map_err(|_| Nip19Error::InvalidKey)
+}
+
+/// Encodes a secret key as an `nsec1...` string.
+///
+/// The result gives full control over the key; handle it as carefully as the key itself.
+pub fn encode_nsec(secret_key: &SecretKey) -> String {
+    bech32::encode(NSEC_PREFIX, secret_key.as_secret_bytes())
+}
+
+/// Decodes an `nsec1...` string into a secret key.
+pub fn decode_nsec(s: &str) -> Result<SecretKey, Nip19Error> {
+    let bytes = decode_32(NSEC_PREFIX, s)?;
+    SecretKey::from_secret_bytes(bytes).map_err(|_| Nip19Error::InvalidKey)
+}
+
+/// Encodes an event ID as a `note1...` string.
+pub fn encode_note(id: &[u8; 32]) -> String { bech32::encode(NOTE_PREFIX, id) }
+
+/// Decodes a `note1...` string into an event ID.
+pub fn decode_note(s: &str) -> Result<[u8; 32], Nip19Error> { decode_32(NOTE_PREFIX, s) }
+
+/// Decodes a bech32 string with human-readable part `prefix` and a 32-byte payload.
+fn decode_32(prefix: &str, s: &str) -> Result<[u8; 32], Nip19Error> {
+    let (hrp, data) = bech32::decode(s).map_err(|_| Nip19Error::InvalidBech32)?;
+    if hrp != prefix {
+        return Err(Nip19Error::WrongPrefix);
+    }
+    <[u8; 32]>::try_from(&data[..]).map_err(|_| Nip19Error::InvalidLength)
+}
+
+/// Errors returned when decoding NIP-19 strings.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Nip19Error {
+    /// The string is not valid bech32.
+    InvalidBech32,
+    /// The human-readable part is not the one expected.
+    WrongPrefix,
+    /// The payload is not 32 bytes long.
+    InvalidLength,
+    /// The payload is not a valid key.
+    InvalidKey,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Nip19Error {}
+
+impl fmt::Display for Nip19Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Nip19Error::InvalidBech32 => f.write_str("invalid bech32 string"),
+            Nip19Error::WrongPrefix => f.write_str("unexpected human-readable part"),
+            Nip19Error::InvalidLength => f.write_str("payload is not 32 bytes long"),
+            Nip19Error::InvalidKey => f.write_str("payload is not a valid key"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    const NPUB: &str = "npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptg";
+    const NSEC: &str = "nsec1vl029mgpspedva04g90vltkh6fvh240zqtv9k0t9af8935ke9laqsnlfe5";
+
+    #[test]
+    fn nip19_vectors() {
+        let pubkey = XOnlyPublicKey::from_byte_array(hex!(
+            "7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e"
+        ))
+        .unwrap();
+        assert_eq!(encode_npub(&pubkey), NPUB);
+        assert_eq!(decode_npub(NPUB), Ok(pubkey));
+
+        let secret_key = SecretKey::from_secret_bytes(hex!(
+            "67dea2ed018072d675f5415ecfaed7d2597555e202d85b3d65ea4e58d2d92ffa"
+        ))
+        .unwrap();
+        assert_eq!(encode_nsec(&secret_key), NSEC);
+        assert_eq!(decode_nsec(NSEC), Ok(secret_key));
+
+        let id = [0xab; 32];
+        assert_eq!(decode_note(&encode_note(&id)), Ok(id));
+    }
+
+    #[test]
+    fn nip19_invalid() {
+        assert_eq!(decode_npub("npub1"), Err(Nip19Error::InvalidBech32));
+        assert_eq!(decode_npub(&NPUB.replace('g', "q")), Err(Nip19Error::InvalidBech32));
+        assert_eq!(decode_npub(NSEC), Err(Nip19Error::WrongPrefix));
+        assert_eq!(decode_nsec(NPUB), Err(Nip19Error::WrongPrefix));
+        assert_eq!(decode_npub(&bech32::encode("npub", &[1; 33])), Err(Nip19Error::InvalidLength));
+        assert_eq!(decode_npub(&bech32::encode("npub", &[0xff; 32])), Err(Nip19Error::InvalidKey));
+        assert_eq!(decode_nsec(&bech32::encode("nsec", &[0; 32])), Err(Nip19Error::InvalidKey));
+    }
+}
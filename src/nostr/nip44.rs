@@ -0,0 +1,334 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! NIP-44 version 2 encrypted payloads.
+//!
+//! Two parties derive the same [`ConversationKey`] from their own secret key and the other's
+//! x-only public key, and use it to encrypt messages with ChaCha20 and authenticate them with
+//! HMAC-SHA256. Message lengths are padded to hide their exact size.
+
+use core::fmt;
+
+use crate::alloc::string::String;
+use crate::alloc::vec;
+use crate::alloc::vec::Vec;
+use crate::hashes::hmac::Hmac;
+use crate::hashes::{hkdf, sha256};
+use crate::{base64, chacha20, ecdh, SecretKey, XOnlyPublicKey};
+
+/// The version byte of the payloads produced by this module.
+const VERSION: u8 = 2;
+
+/// The salt used to derive conversation keys.
+const SALT: &[u8] = b"nip44-v2";
+
+/// The smallest plaintext length, in bytes.
+const MIN_PLAINTEXT_SIZE: usize = 1;
+/// The largest plaintext length, in bytes.
+const MAX_PLAINTEXT_SIZE: usize = 65535;
+
+/// The size of the version byte, nonce and MAC around the ciphertext.
+const OVERHEAD: usize = 1 + 32 + 32;
+
+/// The symmetric key shared by two parties, derived from x-only ECDH.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConversationKey([u8; 32]);
+impl_display_secret!(ConversationKey);
+impl_non_secure_erase!(ConversationKey, 0, [0u8; 32]);
+
+impl ConversationKey {
+    /// Derives the conversation key between the owner of `secret_key` and of `pubkey`.
+    ///
+    /// The result is symmetric: both parties derive the same key from their own secret key and
+    /// the other's public key.
+    pub fn new(secret_key: &SecretKey, pubkey: &XOnlyPublicKey) -> ConversationKey {
+        ConversationKey(hkdf::extract(SALT, &ecdh::shared_x_only(pubkey, secret_key)))
+    }
+
+    /// Creates a conversation key from its byte value.
+    #[inline]
+    pub fn from_bytes(bytes: [u8; 32]) -> ConversationKey { ConversationKey(bytes) }
+
+    /// Returns the conversation key as a byte value.
+    #[inline]
+    pub fn to_secret_bytes(&self) -> [u8; 32] { self.0 }
+
+    /// Derives the ChaCha20 key, ChaCha20 nonce and HMAC key for a message.
+    fn message_keys(&self, nonce: &[u8; 32]) -> ([u8; 32], [u8; 12], [u8; 32]) {
+        let mut keys = [0u8; 76];
+        hkdf::expand(&self.0, nonce, &mut keys);
+        let mut chacha_key = [0u8; 32];
+        let mut chacha_nonce = [0u8; 12];
+        let mut hmac_key = [0u8; 32];
+        chacha_key.copy_from_slice(&keys[..32]);
+        chacha_nonce.copy_from_slice(&keys[32..44]);
+        hmac_key.copy_from_slice(&keys[44..]);
+        (chacha_key, chacha_nonce, hmac_key)
+    }
+}
+
+impl AsRef<[u8]> for ConversationKey {
+    #[inline]
+    fn as_ref(&self) -> &[u8] { &self.0 }
+}
+
+/// Encrypts `plaintext` with a nonce drawn from `rng`.
+#[cfg(feature = "rand")]
+pub fn encrypt_with_rng<R: rand::Rng + rand::CryptoRng + ?Sized>(
+    conversation_key: &ConversationKey,
+    plaintext: &str,
+    rng: &mut R,
+) -> Result<String, Nip44Error> {
+    encrypt(conversation_key, plaintext, &crate::random_32_bytes(rng))
+}
+
+/// Encrypts `plaintext` with the given nonce, returning the base64-encoded payload.
+///
+/// The nonce must never be reused with the same conversation key; prefer [`encrypt_with_rng`]
+/// unless the nonce comes from a cryptographically secure source.
+///
+/// # Errors
+///
+/// Returns [`Nip44Error::InvalidPlaintextLength`] if the plaintext is empty or longer than
+/// 65535 bytes.
+pub fn encrypt(
+    conversation_key: &ConversationKey,
+    plaintext: &str,
+    nonce: &[u8; 32],
+) -> Result<String, Nip44Error> {
+    let len = plaintext.len();
+    if !(MIN_PLAINTEXT_SIZE..=MAX_PLAINTEXT_SIZE).contains(&len) {
+        return Err(Nip44Error::InvalidPlaintextLength);
+    }
+    let (chacha_key, chacha_nonce, hmac_key) = conversation_key.message_keys(nonce);
+
+    let mut payload = vec![0u8; OVERHEAD + 2 + calc_padded_len(len)];
+    payload[0] = VERSION;
+    payload[1..33].copy_from_slice(nonce);
+    let mac_start = payload.len() - 32;
+    let ciphertext = &mut payload[33..mac_start];
+    ciphertext[..2].copy_from_slice(&(len as u16).to_be_bytes());
+    ciphertext[2..2 + len].copy_from_slice(plaintext.as_bytes());
+    chacha20::apply_keystream(&chacha_key, &chacha_nonce, 0, ciphertext);
+
+    let mac = hmac_aad(&hmac_key, nonce, &payload[33..mac_start]);
+    payload[mac_start..].copy_from_slice(&mac);
+
+    let mut encoded = vec![0u8; base64::encoded_len(payload.len())];
+    base64::encode(&payload, &mut encoded).expect("buffer has the encoded length");
+    Ok(String::from_utf8(encoded).expect("base64 is ASCII"))
+}
+
+/// Decrypts a base64-encoded payload produced by [`encrypt`].
+pub fn decrypt(conversation_key: &ConversationKey, payload: &str) -> Result<String, Nip44Error> {
+    if payload.is_empty() || payload.starts_with('#') {
+        return Err(Nip44Error::UnsupportedVersion);
+    }
+    if !(132..=87472).contains(&payload.len()) {
+        return Err(Nip44Error::InvalidPayload);
+    }
+    let mut data = vec![0u8; payload.len() / 4 * 3];
+    let len = base64::decode(payload, &mut data).map_err(|_| Nip44Error::InvalidPayload)?;
+    data.truncate(len);
+    if !(99..=65603).contains(&data.len()) {
+        return Err(Nip44Error::InvalidPayload);
+    }
+    if data[0] != VERSION {
+        return Err(Nip44Error::UnsupportedVersion);
+    }
+
+    let mut nonce = [0u8; 32];
+    nonce.copy_from_slice(&data[1..33]);
+    let mac_start = data.len() - 32;
+    let (chacha_key, chacha_nonce, hmac_key) = conversation_key.message_keys(&nonce);
+    let mac = hmac_aad(&hmac_key, &nonce, &data[33..mac_start]);
+    let diff = mac.iter().zip(&data[mac_start..]).fold(0, |acc, (a, b)| acc | (a ^ b));
+    if unsafe { core::ptr::read_volatile(&diff) } != 0 {
+        return Err(Nip44Error::InvalidMac);
+    }
+
+    let padded = &mut data[33..mac_start];
+    chacha20::apply_keystream(&chacha_key, &chacha_nonce, 0, padded);
+    let len = usize::from(u16::from_be_bytes([padded[0], padded[1]]));
+    if len < MIN_PLAINTEXT_SIZE
+        || padded.len() != 2 + calc_padded_len(len)
+        || padded[2 + len..].iter().any(|&b| b != 0)
+    {
+        return Err(Nip44Error::InvalidPadding);
+    }
+    let plaintext: Vec<u8> = padded[2..2 + len].to_vec();
+    String::from_utf8(plaintext).map_err(|_| Nip44Error::InvalidUtf8)
+}
+
+/// Computes the HMAC-SHA256 of `nonce || ciphertext`.
+fn hmac_aad(key: &[u8; 32], nonce: &[u8; 32], ciphertext: &[u8]) -> [u8; 32] {
+    let mut engine = Hmac::<sha256::HashEngine>::new(key);
+    engine.input(nonce);
+    engine.input(ciphertext);
+    engine.finalize()
+}
+
+/// Returns the padded length of a plaintext of `len` bytes.
+fn calc_padded_len(len: usize) -> usize {
+    if len <= 32 {
+        return 32;
+    }
+    let next_power = 1 << (usize::BITS - (len - 1).leading_zeros());
+    let chunk = if next_power <= 256 { 32 } else { next_power / 8 };
+    chunk * ((len - 1) / chunk + 1)
+}
+
+/// Errors returned when encrypting or decrypting NIP-44 payloads.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Nip44Error {
+    /// The plaintext is empty or longer than 65535 bytes.
+    InvalidPlaintextLength,
+    /// The payload uses an encryption version other than 2.
+    UnsupportedVersion,
+    /// The payload has an invalid length or is not valid base64.
+    InvalidPayload,
+    /// The MAC does not match, the payload was modified or the key is wrong.
+    InvalidMac,
+    /// The decrypted message is not correctly padded.
+    InvalidPadding,
+    /// The decrypted message is not valid UTF-8.
+    InvalidUtf8,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Nip44Error {}
+
+impl fmt::Display for Nip44Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Nip44Error::InvalidPlaintextLength =>
+                f.write_str("plaintext must be between 1 and 65535 bytes long"),
+            Nip44Error::UnsupportedVersion => f.write_str("unsupported encryption version"),
+            Nip44Error::InvalidPayload => f.write_str("malformed payload"),
+            Nip44Error::InvalidMac => f.write_str("invalid MAC"),
+            Nip44Error::InvalidPadding => f.write_str("invalid padding"),
+            Nip44Error::InvalidUtf8 => f.write_str("plaintext is not valid UTF-8"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_lit::hex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    use super::*;
+
+    fn secret_key(n: u8) -> SecretKey {
+        let mut bytes = [0u8; 32];
+        bytes[31] = n;
+        SecretKey::from_secret_bytes(bytes).unwrap()
+    }
+
+    fn xonly(n: u8) -> XOnlyPublicKey { secret_key(n).x_only_public_key().0 }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn conversation_key() {
+        let key = ConversationKey::new(&secret_key(1), &xonly(2));
+        assert_eq!(
+            key.to_secret_bytes(),
+            hex!("c41c775356fd92eadc63ff5a0dc1da211b268cbea22316767095b2871ea1412d")
+        );
+        assert_eq!(ConversationKey::new(&secret_key(2), &xonly(1)), key);
+    }
+
+    #[test]
+    #[cfg(not(secp256k1_fuzz))]
+    fn official_vectors() {
+        let mut nonce = [0u8; 32];
+        nonce[31] = 1;
+        let key = ConversationKey::new(&secret_key(1), &xonly(2));
+        let payload = "AgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABee0G5VSK0/9YypIObAtDKfYEAjD35uVkHyB0F4DwrcNaCXlCWZKaArsGrY6M9wnuTMxWfp1RTN9Xga8no+kF5Vsb";
+        assert_eq!(encrypt(&key, "a", &nonce).unwrap(), payload);
+        assert_eq!(decrypt(&key, payload).unwrap(), "a");
+
+        let nonce = hex!("f00000000000000000000000000000f00000000000000000000000000000000f");
+        let key = ConversationKey::new(&secret_key(2), &xonly(1));
+        let payload = "AvAAAAAAAAAAAAAAAAAAAPAAAAAAAAAAAAAAAAAAAAAPSKSK6is9ngkX2+cSq85Th16oRTISAOfhStnixqZziKMDvB0QQzgFZdjLTPicCJaV8nDITO+QfaQ61+KbWQIOO2Yj";
+        assert_eq!(encrypt(&key, "🍕🫃", &nonce).unwrap(), payload);
+        assert_eq!(decrypt(&key, payload).unwrap(), "🍕🫃");
+    }
+
+    #[test]
+    fn padded_len() {
+        for (len, padded) in [
+            (16, 32),
+            (32, 32),
+            (33, 64),
+            (37, 64),
+            (45, 64),
+            (49, 64),
+            (64, 64),
+            (65, 96),
+            (100, 128),
+            (111, 128),
+            (200, 224),
+            (250, 256),
+            (320, 320),
+            (383, 384),
+            (384, 384),
+            (400, 448),
+            (500, 512),
+            (512, 512),
+            (515, 640),
+            (700, 768),
+            (800, 896),
+            (900, 1024),
+            (1020, 1024),
+            (65535, 65536),
+        ] {
+            assert_eq!(calc_padded_len(len), padded, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn roundtrip_lengths() {
+        let key = ConversationKey::from_bytes([7; 32]);
+        for len in [1, 31, 32, 33, 255, 256, 257, 1000, 65535] {
+            let plaintext = "x".repeat(len);
+            let payload = encrypt(&key, &plaintext, &[9; 32]).unwrap();
+            assert_eq!(decrypt(&key, &payload).unwrap(), plaintext);
+        }
+        assert_eq!(encrypt(&key, "", &[9; 32]), Err(Nip44Error::InvalidPlaintextLength));
+        let long = "x".repeat(65536);
+        assert_eq!(encrypt(&key, &long, &[9; 32]), Err(Nip44Error::InvalidPlaintextLength));
+    }
+
+    #[test]
+    fn decrypt_invalid() {
+        let key = ConversationKey::from_bytes([7; 32]);
+        let payload = encrypt(&key, "hello", &[9; 32]).unwrap();
+
+        assert_eq!(decrypt(&key, ""), Err(Nip44Error::UnsupportedVersion));
+        assert_eq!(decrypt(&key, "#invalid"), Err(Nip44Error::UnsupportedVersion));
+        assert_eq!(decrypt(&key, &payload[..128]), Err(Nip44Error::InvalidPayload));
+        let mut bad_base64 = payload.clone();
+        bad_base64.replace_range(10..11, "!");
+        assert_eq!(decrypt(&key, &bad_base64), Err(Nip44Error::InvalidPayload));
+        assert_eq!(
+            decrypt(&ConversationKey::from_bytes([8; 32]), &payload),
+            Err(Nip44Error::InvalidMac)
+        );
+
+        let mut data = vec![0u8; payload.len()];
+        let len = base64::decode(&payload, &mut data).unwrap();
+        data.truncate(len);
+        let reencode = |data: &[u8]| {
+            let mut encoded = vec![0u8; base64::encoded_len(data.len())];
+            String::from(base64::encode(data, &mut encoded).unwrap())
+        };
+
+        let mut version = data.clone();
+        version[0] = 1;
+        assert_eq!(decrypt(&key, &reencode(&version)), Err(Nip44Error::UnsupportedVersion));
+        let mut tampered = data.clone();
+        tampered[40] ^= 1;
+        assert_eq!(decrypt(&key, &reencode(&tampered)), Err(Nip44Error::InvalidMac));
+    }
+}
@@ -138,6 +138,41 @@ impl From<crate::SecretKey> for Scalar {
     fn from(value: crate::SecretKey) -> Self { Scalar(value.to_secret_bytes()) }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Scalar {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            let mut buf = [0u8; 64];
+            s.serialize_str(crate::to_hex(&self.0, &mut buf).expect("fixed-size hex serialization"))
+        } else {
+            s.serialize_bytes(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Scalar {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        fn parse(slice: &[u8]) -> Result<Scalar, OutOfRangeError> {
+            // A wrong length can't represent a scalar either.
+            let bytes: [u8; 32] = slice.try_into().map_err(|_| OutOfRangeError {})?;
+            Scalar::from_be_bytes(bytes)
+        }
+
+        if d.is_human_readable() {
+            d.deserialize_str(crate::serde_util::HexVisitor::<_, 32>::new(
+                "a hex string representing a 32 byte big-endian scalar",
+                parse,
+            ))
+        } else {
+            d.deserialize_bytes(crate::serde_util::BytesVisitor::new(
+                "raw 32 bytes big-endian scalar",
+                parse,
+            ))
+        }
+    }
+}
+
 /// Error returned when the value of scalar is invalid - larger than the curve order.
 // Intentionally doesn't implement `Copy` to improve forward compatibility.
 // Same reason for `non_exhaustive`.
@@ -154,3 +189,40 @@ impl fmt::Display for OutOfRangeError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for OutOfRangeError {}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    fn serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+        static STR: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+
+        assert_tokens(&Scalar::MAX.compact(), &[Token::BorrowedBytes(&MAX_RAW[..])]);
+        assert_tokens(&Scalar::MAX.compact(), &[Token::Bytes(&MAX_RAW)]);
+        assert_tokens(&Scalar::MAX.compact(), &[Token::ByteBuf(&MAX_RAW)]);
+        assert_tokens(&Scalar::MAX.readable(), &[Token::BorrowedStr(STR)]);
+        assert_tokens(&Scalar::MAX.readable(), &[Token::Str(STR)]);
+        assert_tokens(&Scalar::MAX.readable(), &[Token::String(STR)]);
+
+        assert_de_tokens_error::<serde_test::Compact<Scalar>>(
+            &[Token::Bytes(&constants::CURVE_ORDER)],
+            "the value is not a member of secp256k1 field",
+        );
+        assert_de_tokens_error::<serde_test::Readable<Scalar>>(
+            &[Token::Str("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")],
+            "the value is not a member of secp256k1 field",
+        );
+        assert_de_tokens_error::<serde_test::Readable<Scalar>>(
+            &[Token::Str("zz")],
+            "invalid value: invalid hex string, expected a hex string representing a 32 byte big-endian scalar",
+        );
+    }
+}
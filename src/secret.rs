@@ -15,24 +15,15 @@ macro_rules! impl_display_secret {
             fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
                 use core::fmt;
 
-                use secp256k1_sys::secp256k1_ecdh_hash_function_default;
-
                 struct Hex16Writer([u8; 32]);
 
-                let mut output = Hex16Writer([0u8; 32]);
-                unsafe {
-                    // SAFETY: this function pointer is an Option<fn> so we need to unwrap it, even
-                    // though we know that the FFI has provided a non-null value for us.
-                    let ecdh_hash = secp256k1_ecdh_hash_function_default.unwrap();
-                    // SAFETY: output points to >= 32 mutable bytes; both inputs are >= 32 bytes
-                    assert!(self.as_ref().len() >= 32);
-                    ecdh_hash(
-                        output.0.as_mut_ptr(),
-                        self.as_ref().as_ptr(),
-                        "a debug-only byte 'y coordinate'".as_ptr(),
-                        core::ptr::null_mut(),
-                    );
-                }
+                // Matches the default ECDH hash function applied to the secret as x coordinate
+                // and an odd y coordinate, which is how this output was computed before.
+                let secret: &[u8] = self.as_ref();
+                let mut engine = $crate::hashes::sha256::HashEngine::default();
+                engine.input(&[0x03]);
+                engine.input(&secret[..32]);
+                let output = Hex16Writer(engine.finalize());
 
                 impl fmt::Debug for Hex16Writer {
                     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -65,6 +65,81 @@ where
     }
 }
 
+/// A serde visitor for hex strings that decode to at most `N` bytes.
+///
+/// Useful for types that have no `FromStr` impl, e.g. because their bytes may be secret.
+pub struct HexVisitor<F, const N: usize> {
+    expectation: &'static str,
+    parse_fn: F,
+}
+
+impl<F, T, Err, const N: usize> HexVisitor<F, N>
+where
+    F: FnOnce(&[u8]) -> Result<T, Err>,
+    Err: fmt::Display,
+{
+    pub fn new(expectation: &'static str, parse_fn: F) -> Self {
+        HexVisitor { expectation, parse_fn }
+    }
+}
+
+impl<F, T, Err, const N: usize> de::Visitor<'_> for HexVisitor<F, N>
+where
+    F: FnOnce(&[u8]) -> Result<T, Err>,
+    Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expectation)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let mut buf = [0u8; N];
+        match crate::from_hex(v, &mut buf) {
+            Ok(len) => (self.parse_fn)(&buf[..len]).map_err(E::custom),
+            Err(()) => Err(E::invalid_value(de::Unexpected::Other("invalid hex string"), &self)),
+        }
+    }
+}
+
+/// A serde visitor for unpadded base64url strings that decode to at most `N` bytes.
+pub struct Base64UrlVisitor<F, const N: usize> {
+    expectation: &'static str,
+    parse_fn: F,
+}
+
+impl<F, T, Err, const N: usize> Base64UrlVisitor<F, N>
+where
+    F: FnOnce(&[u8]) -> Result<T, Err>,
+    Err: fmt::Display,
+{
+    pub fn new(expectation: &'static str, parse_fn: F) -> Self {
+        Base64UrlVisitor { expectation, parse_fn }
+    }
+}
+
+impl<F, T, Err, const N: usize> de::Visitor<'_> for Base64UrlVisitor<F, N>
+where
+    F: FnOnce(&[u8]) -> Result<T, Err>,
+    Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expectation)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let mut buf = [0u8; N];
+        match crate::base64::decode_url_safe(v, &mut buf) {
+            Ok(len) => (self.parse_fn)(&buf[..len]).map_err(E::custom),
+            Err(()) =>
+                Err(E::invalid_value(de::Unexpected::Other("invalid base64url string"), &self)),
+        }
+    }
+}
+
 macro_rules! impl_tuple_visitor {
     ($thing:ident, $len:expr) => {
         pub(crate) struct $thing<F> {
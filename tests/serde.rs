@@ -141,3 +141,80 @@ fn musig() {
 
     assert_eq!(ser, MUSIG_PARTIAL_SIG_BYTES);
 }
+
+#[test]
+#[cfg(feature = "recovery")]
+fn recoverable_signature() {
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+
+    let mut bytes = [0x11u8; 65];
+    bytes[64] = RecoveryId::Three.to_u8();
+    let sig = RecoverableSignature::from_byte_array(&bytes).unwrap();
+
+    // bincode prefixes the bytes with an 8-byte length.
+    let ser = bincode::serialize(&sig).unwrap();
+    assert_eq!(ser[..8], [65, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(ser[8..], bytes);
+
+    let cbor = serde_cbor::to_vec(&sig).unwrap();
+    assert_eq!(serde_cbor::from_slice::<RecoverableSignature>(&cbor).unwrap(), sig);
+}
+
+#[test]
+#[cfg(feature = "recovery")]
+fn recoverable_signature_compact_2098() {
+    use secp256k1::ecdsa::{serde_compact_2098, RecoverableSignature, RecoveryId};
+    use serde_test::{assert_tokens, Configure, Token};
+
+    // What `#[serde(with = "secp256k1::ecdsa::serde_compact_2098")]` generates.
+    #[derive(Debug, PartialEq)]
+    struct Wrapper(RecoverableSignature);
+
+    impl serde::Serialize for Wrapper {
+        fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            serde_compact_2098::serialize(&self.0, s)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Wrapper {
+        fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            serde_compact_2098::deserialize(d).map(Wrapper)
+        }
+    }
+
+    let sig = RecoverableSignature::from_compact(&[0x11; 64], RecoveryId::One).unwrap();
+    let mut packed = [0x11u8; 64];
+    packed[32] = 0x91;
+
+    let ser = bincode::serialize(&Wrapper(sig)).unwrap();
+    assert_eq!(ser[..8], [64, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(ser[8..], packed);
+    assert_eq!(bincode::deserialize::<Wrapper>(&ser).unwrap(), Wrapper(sig));
+
+    let cbor = serde_cbor::to_vec(&Wrapper(sig)).unwrap();
+    assert_eq!(serde_cbor::from_slice::<Wrapper>(&cbor).unwrap(), Wrapper(sig));
+
+    let hex = "1111111111111111111111111111111111111111111111111111111111111111\
+               9111111111111111111111111111111111111111111111111111111111111111";
+    assert_tokens(&Wrapper(sig).readable(), &[Token::Str(hex)]);
+
+    let high = RecoverableSignature::from_compact(&[0xee; 64], RecoveryId::One).unwrap();
+    assert!(bincode::serialize(&Wrapper(high)).is_err());
+    let two = RecoverableSignature::from_compact(&[0x11; 64], RecoveryId::Two).unwrap();
+    assert!(bincode::serialize(&Wrapper(two)).is_err());
+}
+
+#[test]
+fn scalar_and_ellswift() {
+    use secp256k1::ellswift::ElligatorSwift;
+    use secp256k1::Scalar;
+
+    let scalar = Scalar::from_be_bytes(SK_BYTES).unwrap();
+    let ser = bincode::serialize(&scalar).unwrap();
+    assert_eq!(ser[8..], SK_BYTES);
+    assert_eq!(bincode::deserialize::<Scalar>(&ser).unwrap(), scalar);
+
+    let ell = ElligatorSwift::from_array([0xab; 64]);
+    let cbor = serde_cbor::to_vec(&ell).unwrap();
+    assert_eq!(serde_cbor::from_slice::<ElligatorSwift>(&cbor).unwrap(), ell);
+}